@@ -17,7 +17,7 @@ use fixed_buffer::FixedBuf;
 /// > - `*` 255 any class
 ///
 /// <https://datatracker.ietf.org/doc/html/rfc1035#section-3.2.4>
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub enum DnsClass {
     Internet,
     Any,
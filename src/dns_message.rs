@@ -1,4 +1,7 @@
-use crate::{DnsError, DnsMessageHeader, DnsQuestion, DnsRecord, DnsResponseCode};
+use crate::dns_name_compressor::DnsNameCompressor;
+use crate::{
+    DnsClass, DnsError, DnsMessageHeader, DnsName, DnsQuestion, DnsRecord, DnsResponseCode, DnsType,
+};
 use fixed_buffer::FixedBuf;
 use std::convert::TryFrom;
 
@@ -17,6 +20,9 @@ impl DnsMessage {
         u16::try_from(self.questions.len()).map_err(|_| DnsError::TooManyQuestions)
     }
 
+    /// Reads a message.  The message must start at the beginning of `buf`'s memory, because
+    /// compressed names contain offsets from the start of the message.
+    ///
     /// # Errors
     /// Returns an error when `buf` does not contain a valid message.
     pub fn read<const N: usize>(buf: &mut FixedBuf<N>) -> Result<Self, DnsError> {
@@ -54,12 +60,16 @@ impl DnsMessage {
         })
     }
 
+    /// Writes the message.  Compresses names that repeat a suffix of an earlier name in any
+    /// section.
+    ///
     /// # Errors
     /// Returns an error when `buf` fills up.
     pub fn write<const N: usize>(&self, out: &mut FixedBuf<N>) -> Result<(), DnsError> {
+        let mut compressor = DnsNameCompressor::new(out);
         self.header.write(out)?;
         for question in &self.questions {
-            question.write(out)?;
+            question.write_compressed(out, &mut compressor)?;
         }
         for record in self
             .answers
@@ -67,11 +77,15 @@ impl DnsMessage {
             .chain(self.name_servers.iter())
             .chain(self.additional.iter())
         {
-            record.write(out)?;
+            record.write_compressed(out, &mut compressor)?;
         }
         Ok(())
     }
 
+    /// Makes a response with `answers` and empty authority and additional sections.
+    /// Does not add glue or other address records to the additional section.
+    /// Use [`lookup_response`](Self::lookup_response) for that.
+    ///
     /// # Errors
     /// Returns an error when there are more than 65,536 questions.
     pub fn answer_response<'x>(
@@ -102,6 +116,274 @@ impl DnsMessage {
         })
     }
 
+    /// Makes a response that answers the first question with matching records from `records`.
+    ///
+    /// A record matches when its name equals the question's name, its class equals the question's
+    /// class or the question's class is ANY, and its type equals the question's type, is CNAME,
+    /// or the question's type is ANY.
+    ///
+    /// When no record has the question's name or a name below it, uses wildcard records.
+    /// This follows RFC 4592:
+    ///
+    /// > The source of synthesis is a wildcard domain name whose parent is the closest encloser
+    /// > of the query name.  ...  The closest encloser is the longest existing ancestor of the
+    /// > query name.
+    ///
+    /// Synthesized answers have the question's name in place of the wildcard name.
+    /// A name that exists, even without records of the question's type, blocks synthesis.
+    ///
+    /// <https://datatracker.ietf.org/doc/html/rfc4592#section-3.3.1>
+    ///
+    /// The zone's apex is the closest name at or above the question's name with an SOA record,
+    /// or else the highest one with NS records.
+    /// NS records at a name below the apex delegate that name to other servers.
+    /// When the question's name is at or below a delegation, returns a referral:
+    ///
+    /// > If a match would take us out of the authoritative data, we have a referral.  This
+    /// > happens when we encounter a node with NS RRs marking cuts along the bottom of a zone.
+    /// >
+    /// > Copy the NS RRs for the subzone into the authority section of the reply.  Put whatever
+    /// > addresses are available into the additional section, using glue RRs if the addresses
+    /// > are not available from authoritative data or the cache.
+    ///
+    /// <https://datatracker.ietf.org/doc/html/rfc1034#section-4.3.2>
+    ///
+    /// When the question's name is below the owner of a DNAME record, answers with the DNAME
+    /// and a CNAME synthesized from it, as RFC 6672 section 3.2 describes.
+    /// Never follows the synthesized CNAME's target, even when the target is in the zone,
+    /// so the resolver must query the target itself.
+    /// Returns a `YxDomain` response when the synthesized name is too long.
+    ///
+    /// <https://datatracker.ietf.org/doc/html/rfc6672#section-3.2>
+    ///
+    /// Adds the A and AAAA records of name servers that are in the zone to the additional
+    /// section, for NS records in the answer or authority section.
+    /// Also adds the A and AAAA records of the targets of SRV answers:
+    ///
+    /// > Implementors are urged, but not required, to return the address record(s) in the
+    /// > Additional Data section.
+    ///
+    /// <https://datatracker.ietf.org/doc/html/rfc2782>
+    ///
+    /// Returns a `NameError` response when the name does not exist.
+    /// Returns a response with no answers when the name exists but has no matching records.
+    /// Both of these negative responses carry the zone's SOA record in the authority section:
+    ///
+    /// > Name servers authoritative for a zone MUST include the SOA record of the zone in the
+    /// > authority section of the response when reporting an NXDOMAIN or indicating that no data
+    /// > of the requested type exists.  This is required so that the response may be cached.
+    /// > The TTL of this record is set from the minimum of the MINIMUM field of the SOA record
+    /// > and the TTL of the SOA itself, and indicates how long a resolver may cache the negative
+    /// > answer.
+    ///
+    /// <https://datatracker.ietf.org/doc/html/rfc2308#section-3>
+    ///
+    /// # Errors
+    /// Returns an error when the message has no questions or the response is too big.
+    pub fn lookup_response(&self, records: &[DnsRecord]) -> Result<Self, DnsError> {
+        let question = self.questions.first().ok_or(DnsError::NoQuestion)?;
+        let in_class = |record: &&DnsRecord| {
+            question.class == DnsClass::Any || record.class() == question.class
+        };
+        let name_servers_at = |owner: &DnsName| -> Vec<&DnsRecord> {
+            records
+                .iter()
+                .filter(in_class)
+                .filter(|record| record.typ() == DnsType::NS && record.name() == owner)
+                .collect()
+        };
+        // The question's name and its ancestors, from the root down.
+        let mut ancestors: Vec<DnsName> =
+            std::iter::successors(Some(question.name.clone()), DnsName::parent).collect();
+        ancestors.reverse();
+        let soa_at = |owner: &DnsName| {
+            records
+                .iter()
+                .filter(in_class)
+                .find(|record| record.typ() == DnsType::SOA && record.name() == owner)
+        };
+        let apex_index = ancestors
+            .iter()
+            .rposition(|name| soa_at(name).is_some())
+            .or_else(|| {
+                ancestors
+                    .iter()
+                    .position(|name| !name_servers_at(name).is_empty())
+            });
+        let zone_cut = apex_index.and_then(|apex_index| {
+            ancestors[apex_index + 1..]
+                .iter()
+                .find(|name| !name_servers_at(name).is_empty())
+        });
+        let apex = apex_index.map(|apex_index| &ancestors[apex_index]);
+        let negative_soa: Vec<DnsRecord> = apex
+            .and_then(soa_at)
+            .map(|soa| match soa {
+                DnsRecord::SOA { ttl, minimum, .. } => soa.clone().with_ttl(*ttl.min(minimum)),
+                _ => soa.clone(),
+            })
+            .into_iter()
+            .collect();
+        // A DNAME redirects names below its owner, by substituting its target for its owner.
+        // A zone cut at or above the DNAME's owner takes precedence.
+        // https://datatracker.ietf.org/doc/html/rfc6672#section-3.2
+        let dname = ancestors[..ancestors.len() - 1]
+            .iter()
+            .skip(apex_index.unwrap_or(0))
+            .find_map(|owner| {
+                records
+                    .iter()
+                    .filter(in_class)
+                    .find(|record| record.typ() == DnsType::DNAME && record.name() == owner)
+            })
+            .filter(|dname| {
+                zone_cut.is_none_or(|zone_cut| zone_cut.num_labels() > dname.name().num_labels())
+            });
+        if let Some(
+            dname @ DnsRecord::DNAME {
+                name: owner,
+                class,
+                ttl,
+                target,
+            },
+        ) = dname
+        {
+            let synthesized = question
+                .name
+                .strip_suffix(owner)
+                .ok_or(DnsError::Unreachable(file!(), line!()))?
+                .append(target);
+            return match synthesized {
+                Ok(synthesized) => {
+                    let cname = DnsRecord::CNAME {
+                        name: question.name.clone(),
+                        class: *class,
+                        ttl: *ttl,
+                        target: synthesized,
+                    };
+                    self.answer_response([dname.clone(), cname].iter())
+                }
+                // The synthesized name is too long.
+                // https://datatracker.ietf.org/doc/html/rfc6672#section-2.2
+                Err(_) => {
+                    let mut response = self.answer_response([dname].into_iter())?;
+                    response.header.response_code = DnsResponseCode::YxDomain;
+                    Ok(response)
+                }
+            };
+        }
+        if let Some(zone_cut) = zone_cut {
+            let name_servers: Vec<DnsRecord> =
+                name_servers_at(zone_cut).into_iter().cloned().collect();
+            let mut response = self.answer_response([].iter())?;
+            response.header.authoritative_answer = false;
+            response.set_additional(Self::additional(&name_servers, apex, records, in_class))?;
+            response.set_name_servers(name_servers)?;
+            return Ok(response);
+        }
+        let name_exists = |name: &DnsName| {
+            records
+                .iter()
+                .filter(in_class)
+                .any(|record| record.name().is_subdomain_of(name))
+        };
+        let matching = |owner: &DnsName| -> Vec<&DnsRecord> {
+            records
+                .iter()
+                .filter(in_class)
+                .filter(|record| {
+                    record.name() == owner
+                        && (question.typ == DnsType::ANY
+                            || record.typ() == question.typ
+                            || record.typ() == DnsType::CNAME)
+                })
+                .collect()
+        };
+        let answers: Vec<DnsRecord> = if name_exists(&question.name) {
+            matching(&question.name).into_iter().cloned().collect()
+        } else {
+            let mut closest_encloser = question.name.parent();
+            while let Some(name) = &closest_encloser {
+                if name_exists(name) {
+                    break;
+                }
+                closest_encloser = name.parent();
+            }
+            let source_of_synthesis = closest_encloser
+                .as_ref()
+                .and_then(DnsName::wildcard_child)
+                .filter(|wildcard| {
+                    records
+                        .iter()
+                        .filter(in_class)
+                        .any(|record| record.name() == wildcard)
+                });
+            match source_of_synthesis {
+                Some(wildcard) => matching(&wildcard)
+                    .into_iter()
+                    .map(|record| record.clone().with_name(question.name.clone()))
+                    .collect(),
+                None => {
+                    let mut response = self.error_response(DnsResponseCode::NameError)?;
+                    response.set_name_servers(negative_soa)?;
+                    return Ok(response);
+                }
+            }
+        };
+        let mut response = self.answer_response(answers.iter())?;
+        if answers.is_empty() {
+            response.set_name_servers(negative_soa)?;
+        }
+        response.set_additional(Self::additional(&answers, apex, records, in_class))?;
+        Ok(response)
+    }
+
+    /// Returns the A and AAAA records in `records` for the hosts of the NS records in
+    /// `section` that are at or below `apex`, and for the targets of the SRV records in `section`.
+    /// Uses only the records for which `in_class` returns true.
+    fn additional(
+        section: &[DnsRecord],
+        apex: Option<&DnsName>,
+        records: &[DnsRecord],
+        in_class: impl Fn(&&DnsRecord) -> bool,
+    ) -> Vec<DnsRecord> {
+        let mut additional: Vec<DnsRecord> = Vec::new();
+        for section_record in section {
+            let host = match section_record {
+                DnsRecord::NS { host, .. }
+                    if apex.is_some_and(|apex| host.is_subdomain_of(apex)) =>
+                {
+                    host
+                }
+                DnsRecord::SRV { target, .. } => target,
+                _ => continue,
+            };
+            for record in records.iter().filter(&in_class) {
+                if record.name() == host
+                    && (record.typ() == DnsType::A || record.typ() == DnsType::AAAA)
+                    && !additional.contains(record)
+                {
+                    additional.push(record.clone());
+                }
+            }
+        }
+        additional
+    }
+
+    fn set_name_servers(&mut self, name_servers: Vec<DnsRecord>) -> Result<(), DnsError> {
+        self.header.name_server_count =
+            u16::try_from(name_servers.len()).map_err(|_| DnsError::TooManyNameServers)?;
+        self.name_servers = name_servers;
+        Ok(())
+    }
+
+    fn set_additional(&mut self, additional: Vec<DnsRecord>) -> Result<(), DnsError> {
+        self.header.additional_count =
+            u16::try_from(additional.len()).map_err(|_| DnsError::TooManyAdditional)?;
+        self.additional = additional;
+        Ok(())
+    }
+
     /// # Errors
     /// Returns an error when there are more than 65,536 questions.
     pub fn error_response(&self, response_code: DnsResponseCode) -> Result<Self, DnsError> {
@@ -127,3 +409,564 @@ impl DnsMessage {
         })
     }
 }
+
+#[cfg(test)]
+const COMPRESSED_RESPONSE: [u8; 67] = [
+    0x12, 0x34, 0x81, 0x80, 0, 1, 0, 2, 0, 0, 0, 0, // header
+    3, b'w', b'w', b'w', 7, b'e', b'x', b'a', b'm', b'p', b'l', b'e', 3, b'c', b'o', b'm', 0, 0, 1,
+    0, 1, // question
+    0xC0, 12, 0, 5, 0, 1, 0, 0, 1, 44, 0, 6, 3, b'w', b'e', b'b', 0xC0, 16, // CNAME
+    0xC0, 45, 0, 1, 0, 1, 0, 0, 1, 44, 0, 4, 93, 184, 216, 34, // A
+];
+
+#[cfg(test)]
+#[test]
+fn test_read_compressed_response() {
+    use crate::DnsClass;
+    let mut buf: FixedBuf<512> = FixedBuf::new();
+    buf.write_bytes(&COMPRESSED_RESPONSE).unwrap();
+    let message = DnsMessage::read(&mut buf).unwrap();
+    assert!(buf.is_empty());
+    assert_eq!(
+        vec![DnsQuestion {
+            name: DnsName::new("www.example.com").unwrap(),
+            typ: DnsType::A,
+            class: DnsClass::Internet
+        }],
+        message.questions
+    );
+    assert_eq!(
+        vec![
+            DnsRecord::new_cname("www.example.com", "web.example.com").unwrap(),
+            DnsRecord::new_a("web.example.com", "93.184.216.34").unwrap(),
+        ],
+        message.answers
+    );
+}
+
+#[cfg(test)]
+#[test]
+fn test_write_compressed() {
+    let mut buf: FixedBuf<512> = FixedBuf::new();
+    buf.write_bytes(&COMPRESSED_RESPONSE).unwrap();
+    let message = DnsMessage::read(&mut buf).unwrap();
+    let mut out: FixedBuf<512> = FixedBuf::new();
+    message.write(&mut out).unwrap();
+    assert_eq!(&COMPRESSED_RESPONSE[..], out.readable());
+}
+
+#[cfg(test)]
+fn query(name: &str, typ: DnsType) -> DnsMessage {
+    use crate::DnsOpCode;
+    DnsMessage {
+        header: DnsMessageHeader {
+            id: 7,
+            is_response: false,
+            op_code: DnsOpCode::Query,
+            authoritative_answer: false,
+            truncated: false,
+            recursion_desired: false,
+            recursion_available: false,
+            response_code: DnsResponseCode::NoError,
+            question_count: 1,
+            answer_count: 0,
+            name_server_count: 0,
+            additional_count: 0,
+        },
+        questions: vec![DnsQuestion {
+            name: DnsName::new(name).unwrap(),
+            typ,
+            class: DnsClass::Internet,
+        }],
+        answers: Vec::new(),
+        name_servers: Vec::new(),
+        additional: Vec::new(),
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_read_write_opt() {
+    // A query with an EDNS OPT pseudo-record: UDP payload size 4096 and the DO bit.
+    let bytes = [
+        0x12, 0x34, 0x01, 0x00, 0, 1, 0, 0, 0, 0, 0, 1, // header
+        3, b'w', b'w', b'w', 7, b'e', b'x', b'a', b'm', b'p', b'l', b'e', 3, b'c', b'o', b'm', 0,
+        0, 1, 0, 1, // question
+        0, 0, 41, 0x10, 0x00, 0x00, 0x00, 0x80, 0x00, 0, 0, // OPT
+    ];
+    let mut buf: FixedBuf<512> = FixedBuf::new();
+    buf.write_bytes(&bytes).unwrap();
+    let message = DnsMessage::read(&mut buf).unwrap();
+    assert_eq!(
+        vec![DnsRecord::Unknown {
+            name: DnsName::root(),
+            class: DnsClass::Unknown(4096),
+            ttl: 0x8000,
+            typ: DnsType::Unknown(41),
+            rdata: Vec::new(),
+        }],
+        message.additional
+    );
+    let mut out: FixedBuf<512> = FixedBuf::new();
+    message.write(&mut out).unwrap();
+    assert_eq!(&bytes[..], out.readable());
+}
+
+#[cfg(test)]
+#[test]
+fn test_read_write_chaos_question() {
+    // A `version.bind CH TXT` query.
+    let bytes = [
+        0x12, 0x34, 0x01, 0x00, 0, 1, 0, 0, 0, 0, 0, 0, // header
+        7, b'v', b'e', b'r', b's', b'i', b'o', b'n', 4, b'b', b'i', b'n', b'd', 0, 0, 16, 0,
+        3, // question
+    ];
+    let mut buf: FixedBuf<512> = FixedBuf::new();
+    buf.write_bytes(&bytes).unwrap();
+    let message = DnsMessage::read(&mut buf).unwrap();
+    assert_eq!(
+        vec![DnsQuestion {
+            name: DnsName::new("version.bind").unwrap(),
+            typ: DnsType::TXT,
+            class: DnsClass::Unknown(3),
+        }],
+        message.questions
+    );
+    let mut out: FixedBuf<512> = FixedBuf::new();
+    message.write(&mut out).unwrap();
+    assert_eq!(&bytes[..], out.readable());
+}
+
+#[cfg(test)]
+#[test]
+fn test_write_compressed_many_answers() {
+    let name = "a-rather-long-host-name.with-a-long-subdomain.example.com";
+    let records: Vec<DnsRecord> = (1..=20)
+        .map(|n| DnsRecord::new_a(name, &format!("10.0.0.{n}")).unwrap())
+        .collect();
+    let response = query(name, DnsType::A)
+        .answer_response(records.iter())
+        .unwrap();
+    let mut out: FixedBuf<512> = FixedBuf::new();
+    response.write(&mut out).unwrap();
+    // Header, question, and 20 answers that each start with a two-byte pointer.
+    assert_eq!(12 + (name.len() + 2 + 4) + 20 * (2 + 10 + 4), out.len());
+    let mut buf: FixedBuf<512> = FixedBuf::new();
+    buf.write_bytes(out.readable()).unwrap();
+    assert_eq!(response, DnsMessage::read(&mut buf).unwrap());
+}
+
+#[cfg(test)]
+#[test]
+fn test_lookup_response() {
+    let records = [
+        DnsRecord::new_a("a.example.com", "10.0.0.1").unwrap(),
+        DnsRecord::new_a("a.example.com", "10.0.0.2").unwrap(),
+        DnsRecord::new_aaaa("a.example.com", "2001:db8::1").unwrap(),
+        DnsRecord::new_cname("b.example.com", "a.example.com").unwrap(),
+        DnsRecord::new_a("c.d.example.com", "10.0.0.3").unwrap(),
+        DnsRecord::new_a("a.example.com", "10.0.0.4")
+            .unwrap()
+            .with_class(DnsClass::Unknown(3)),
+    ];
+    let response = query("a.example.com", DnsType::A)
+        .lookup_response(&records)
+        .unwrap();
+    assert_eq!(DnsResponseCode::NoError, response.header.response_code);
+    assert_eq!(&records[0..2], response.answers.as_slice());
+    assert_eq!(2, response.header.answer_count);
+    let response = query("a.example.com", DnsType::ANY)
+        .lookup_response(&records)
+        .unwrap();
+    assert_eq!(&records[0..3], response.answers.as_slice());
+    let response = query("b.example.com", DnsType::A)
+        .lookup_response(&records)
+        .unwrap();
+    assert_eq!(&records[3..4], response.answers.as_slice());
+    // Exists, but no records of that type.
+    let response = query("a.example.com", DnsType::MX)
+        .lookup_response(&records)
+        .unwrap();
+    assert_eq!(DnsResponseCode::NoError, response.header.response_code);
+    assert!(response.answers.is_empty());
+    // Empty non-terminal.
+    let response = query("d.example.com", DnsType::A)
+        .lookup_response(&records)
+        .unwrap();
+    assert_eq!(DnsResponseCode::NoError, response.header.response_code);
+    assert!(response.answers.is_empty());
+    let response = query("x.example.com", DnsType::A)
+        .lookup_response(&records)
+        .unwrap();
+    assert_eq!(DnsResponseCode::NameError, response.header.response_code);
+    assert!(response.answers.is_empty());
+}
+
+#[cfg(test)]
+#[test]
+fn test_lookup_response_wildcard() {
+    let records = [
+        DnsRecord::new_a("*.preview.example.com", "10.0.0.1").unwrap(),
+        DnsRecord::new_aaaa("*.preview.example.com", "2001:db8::1").unwrap(),
+        DnsRecord::new_a("main.preview.example.com", "10.0.0.2").unwrap(),
+        DnsRecord::new_a("sub.main.preview.example.com", "10.0.0.3").unwrap(),
+        DnsRecord::new_a("x.y.preview.example.com", "10.0.0.4").unwrap(),
+    ];
+    let lookup = |name: &str, typ: DnsType| query(name, typ).lookup_response(&records).unwrap();
+    // Synthesized from the wildcard, with the owner name rewritten.
+    let response = lookup("feature-1.preview.example.com", DnsType::A);
+    assert_eq!(DnsResponseCode::NoError, response.header.response_code);
+    assert_eq!(
+        vec![DnsRecord::new_a("feature-1.preview.example.com", "10.0.0.1").unwrap()],
+        response.answers
+    );
+    // Matches more than one label.
+    let response = lookup("a.b.preview.example.com", DnsType::AAAA);
+    assert_eq!(
+        vec![DnsRecord::new_aaaa("a.b.preview.example.com", "2001:db8::1").unwrap()],
+        response.answers
+    );
+    // Wildcard exists but has no records of the type.
+    let response = lookup("feature-1.preview.example.com", DnsType::MX);
+    assert_eq!(DnsResponseCode::NoError, response.header.response_code);
+    assert!(response.answers.is_empty());
+    // Existing names are not synthesized.
+    assert_eq!(
+        &records[2..3],
+        lookup("main.preview.example.com", DnsType::A).answers
+    );
+    let response = lookup("main.preview.example.com", DnsType::AAAA);
+    assert_eq!(DnsResponseCode::NoError, response.header.response_code);
+    assert!(response.answers.is_empty());
+    // Empty non-terminals are not synthesized.
+    let response = lookup("y.preview.example.com", DnsType::A);
+    assert_eq!(DnsResponseCode::NoError, response.header.response_code);
+    assert!(response.answers.is_empty());
+    // The closest encloser has no wildcard child.
+    let response = lookup("other.main.preview.example.com", DnsType::A);
+    assert_eq!(DnsResponseCode::NameError, response.header.response_code);
+    let response = lookup("other.y.preview.example.com", DnsType::A);
+    assert_eq!(DnsResponseCode::NameError, response.header.response_code);
+    let response = lookup("other.example.com", DnsType::A);
+    assert_eq!(DnsResponseCode::NameError, response.header.response_code);
+    // Querying the wildcard name itself.
+    assert_eq!(
+        &records[0..1],
+        lookup("*.preview.example.com", DnsType::A).answers
+    );
+}
+
+#[cfg(test)]
+#[test]
+fn test_lookup_response_mixed_case() {
+    let records = [DnsRecord::new_a("www.example.com", "10.0.0.1").unwrap()];
+    let request = query("wWw.ExAmPlE.cOm", DnsType::A);
+    let mut request_bytes: FixedBuf<512> = FixedBuf::new();
+    request.write(&mut request_bytes).unwrap();
+    let mut buf: FixedBuf<512> = FixedBuf::new();
+    buf.write_bytes(request_bytes.readable()).unwrap();
+    let response = DnsMessage::read(&mut buf)
+        .unwrap()
+        .lookup_response(&records)
+        .unwrap();
+    assert_eq!(&records[..], response.answers.as_slice());
+    let mut out: FixedBuf<512> = FixedBuf::new();
+    response.write(&mut out).unwrap();
+    // The question is echoed byte-for-byte.
+    let question_len = "wWw.ExAmPlE.cOm".len() + 2 + 4;
+    assert_eq!(
+        &request_bytes.readable()[12..12 + question_len],
+        &out.readable()[12..12 + question_len]
+    );
+}
+
+#[cfg(test)]
+#[test]
+fn test_lookup_response_delegation() {
+    let records = [
+        DnsRecord::new_ns("example.com", "ns1.example.com").unwrap(),
+        DnsRecord::new_ns("example.com", "ns.other.net").unwrap(),
+        DnsRecord::new_a("ns1.example.com", "10.0.0.1").unwrap(),
+        DnsRecord::new_a("www.example.com", "10.0.0.2").unwrap(),
+        DnsRecord::new_ns("sub.example.com", "ns1.sub.example.com").unwrap(),
+        DnsRecord::new_ns("sub.example.com", "ns2.sub.example.com").unwrap(),
+        DnsRecord::new_ns("sub.example.com", "ns.other.net").unwrap(),
+        DnsRecord::new_a("ns1.sub.example.com", "10.0.1.1").unwrap(),
+        DnsRecord::new_aaaa("ns1.sub.example.com", "2001:db8::1").unwrap(),
+        DnsRecord::new_a("ns2.sub.example.com", "10.0.1.2").unwrap(),
+        DnsRecord::new_a("ns.other.net", "192.0.2.1").unwrap(),
+    ];
+    let lookup = |name: &str, typ: DnsType| query(name, typ).lookup_response(&records).unwrap();
+    // NS records at the apex are answers, with glue.
+    let response = lookup("example.com", DnsType::NS);
+    assert!(response.header.authoritative_answer);
+    assert_eq!(&records[0..2], response.answers.as_slice());
+    assert_eq!(&records[2..3], response.additional.as_slice());
+    assert_eq!(1, response.header.additional_count);
+    assert!(response.name_servers.is_empty());
+    // Names at and below a delegation get referrals.
+    for (name, typ) in [
+        ("sub.example.com", DnsType::NS),
+        ("sub.example.com", DnsType::A),
+        ("www.sub.example.com", DnsType::A),
+        ("ns1.sub.example.com", DnsType::A),
+    ] {
+        let response = lookup(name, typ);
+        assert_eq!(DnsResponseCode::NoError, response.header.response_code);
+        assert!(!response.header.authoritative_answer, "{name}");
+        assert!(response.answers.is_empty(), "{name}");
+        assert_eq!(&records[4..7], response.name_servers.as_slice(), "{name}");
+        assert_eq!(3, response.header.name_server_count);
+        assert_eq!(&records[7..10], response.additional.as_slice(), "{name}");
+        assert_eq!(3, response.header.additional_count);
+    }
+    // Other names are answered normally.
+    let response = lookup("www.example.com", DnsType::A);
+    assert!(response.header.authoritative_answer);
+    assert_eq!(&records[3..4], response.answers.as_slice());
+    assert!(response.name_servers.is_empty());
+    assert!(response.additional.is_empty());
+    // Round trip.
+    let response = lookup("www.sub.example.com", DnsType::A);
+    let mut out: FixedBuf<512> = FixedBuf::new();
+    response.write(&mut out).unwrap();
+    assert_eq!(response, DnsMessage::read(&mut out).unwrap());
+}
+
+#[cfg(test)]
+#[test]
+fn test_lookup_response_other_class() {
+    let chaos = DnsClass::Unknown(3);
+    let records = [
+        DnsRecord::new_ns("example.com", "ns1.example.com").unwrap(),
+        DnsRecord::new_a("ns1.example.com", "10.0.0.1").unwrap(),
+        DnsRecord::new_a("ns1.example.com", "10.0.0.2")
+            .unwrap()
+            .with_class(chaos),
+        DnsRecord::new_srv("_x._tcp.example.com", 1, 1, 1, "host.example.com").unwrap(),
+        DnsRecord::new_a("host.example.com", "10.0.0.3")
+            .unwrap()
+            .with_class(chaos),
+        DnsRecord::new_a("*.wild.example.com", "10.0.0.4")
+            .unwrap()
+            .with_class(chaos),
+    ];
+    let lookup = |name: &str, typ: DnsType| query(name, typ).lookup_response(&records).unwrap();
+    // Glue and SRV target addresses come from the question's class.
+    let response = lookup("example.com", DnsType::NS);
+    assert_eq!(&records[1..2], response.additional.as_slice());
+    let response = lookup("_x._tcp.example.com", DnsType::SRV);
+    assert_eq!(&records[3..4], response.answers.as_slice());
+    assert!(response.additional.is_empty());
+    // Names and wildcards exist only in their records' class.
+    for name in ["host.example.com", "a.wild.example.com"] {
+        let response = lookup(name, DnsType::A);
+        assert_eq!(DnsResponseCode::NameError, response.header.response_code);
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_lookup_response_negative() {
+    let soa = DnsRecord::new_soa(
+        "example.com",
+        "ns1.example.com",
+        "hostmaster@example.com",
+        1,
+    )
+    .unwrap()
+    .with_ttl(86400);
+    let records = [
+        soa.clone(),
+        DnsRecord::new_ns("example.com", "ns1.example.com").unwrap(),
+        DnsRecord::new_a("ns1.example.com", "10.0.0.1").unwrap(),
+        DnsRecord::new_a("www.example.com", "10.0.0.2").unwrap(),
+        DnsRecord::new_soa(
+            "other.example.com",
+            "ns1.example.com",
+            "hostmaster@example.com",
+            2,
+        )
+        .unwrap()
+        .with_ttl(60),
+    ];
+    let lookup = |name: &str, typ: DnsType| query(name, typ).lookup_response(&records).unwrap();
+    // TTL is min(SOA TTL, MINIMUM).
+    let negative_soa = soa.clone().with_ttl(3600);
+    let response = lookup("missing.example.com", DnsType::A);
+    assert_eq!(DnsResponseCode::NameError, response.header.response_code);
+    assert_eq!(vec![negative_soa.clone()], response.name_servers);
+    assert_eq!(1, response.header.name_server_count);
+    let response = lookup("www.example.com", DnsType::AAAA);
+    assert_eq!(DnsResponseCode::NoError, response.header.response_code);
+    assert!(response.answers.is_empty());
+    assert_eq!(vec![negative_soa], response.name_servers);
+    assert_eq!(1, response.header.name_server_count);
+    // Uses the closest zone's SOA.
+    let response = lookup("missing.other.example.com", DnsType::A);
+    assert_eq!(DnsResponseCode::NameError, response.header.response_code);
+    assert_eq!(&records[4..5], response.name_servers.as_slice());
+    // Positive answers have no SOA.
+    let response = lookup("www.example.com", DnsType::A);
+    assert_eq!(&records[3..4], response.answers.as_slice());
+    assert!(response.name_servers.is_empty());
+    assert_eq!(0, response.header.name_server_count);
+    let response = lookup("example.com", DnsType::SOA);
+    assert_eq!(vec![soa], response.answers);
+    assert!(response.name_servers.is_empty());
+    // Names outside of any zone have no SOA.
+    let response = query("example.net", DnsType::A)
+        .lookup_response(&records)
+        .unwrap();
+    assert_eq!(DnsResponseCode::NameError, response.header.response_code);
+    assert!(response.name_servers.is_empty());
+    // Round trip.
+    let response = lookup("missing.example.com", DnsType::A);
+    let mut out: FixedBuf<512> = FixedBuf::new();
+    response.write(&mut out).unwrap();
+    assert_eq!(response, DnsMessage::read(&mut out).unwrap());
+}
+
+#[cfg(test)]
+#[test]
+fn test_lookup_response_reverse() {
+    let records = [
+        DnsRecord::new_a("device-1.example.com", "10.0.0.1").unwrap(),
+        DnsRecord::new_ptr("10.0.0.1", "device-1.example.com").unwrap(),
+        DnsRecord::new_aaaa("device-1.example.com", "2001:db8::1").unwrap(),
+        DnsRecord::new_ptr("2001:db8::1", "device-1.example.com").unwrap(),
+    ];
+    let lookup = |name: &DnsName| {
+        let mut request = query("example.com", DnsType::PTR);
+        request.questions[0].name = name.clone();
+        request.lookup_response(&records).unwrap()
+    };
+    let response = lookup(&DnsName::from_ip("10.0.0.1".parse().unwrap()));
+    assert_eq!(&records[1..2], response.answers.as_slice());
+    let response = lookup(&DnsName::from_ip("2001:db8::1".parse().unwrap()));
+    assert_eq!(&records[3..4], response.answers.as_slice());
+    let response = lookup(&DnsName::from_ip("10.0.0.2".parse().unwrap()));
+    assert_eq!(DnsResponseCode::NameError, response.header.response_code);
+}
+
+#[cfg(test)]
+#[test]
+fn test_lookup_response_srv() {
+    let records = [
+        DnsRecord::new_srv("_mqtt._tcp.example.com", 10, 5, 8883, "broker1.example.com").unwrap(),
+        DnsRecord::new_srv("_mqtt._tcp.example.com", 20, 5, 8883, "broker2.example.com").unwrap(),
+        DnsRecord::new_srv("_mqtt._tcp.example.com", 30, 5, 8883, "broker.other.net").unwrap(),
+        DnsRecord::new_a("broker1.example.com", "10.0.0.1").unwrap(),
+        DnsRecord::new_aaaa("broker1.example.com", "2001:db8::1").unwrap(),
+        DnsRecord::new_a("broker2.example.com", "10.0.0.2").unwrap(),
+        DnsRecord::new_a("other.example.com", "10.0.0.3").unwrap(),
+    ];
+    let response = query("_mqtt._tcp.example.com", DnsType::SRV)
+        .lookup_response(&records)
+        .unwrap();
+    assert_eq!(&records[0..3], response.answers.as_slice());
+    assert_eq!(&records[3..6], response.additional.as_slice());
+    assert_eq!(3, response.header.additional_count);
+    let mut out: FixedBuf<512> = FixedBuf::new();
+    response.write(&mut out).unwrap();
+    assert_eq!(response, DnsMessage::read(&mut out).unwrap());
+}
+
+#[cfg(test)]
+#[test]
+fn test_lookup_response_dname() {
+    let soa = DnsRecord::new_soa(
+        "example.com",
+        "ns1.example.com",
+        "hostmaster@example.com",
+        1,
+    )
+    .unwrap();
+    let dname = DnsRecord::new_dname("old.example.com", "new.example.com")
+        .unwrap()
+        .with_ttl(3600);
+    let records = [
+        soa.clone(),
+        dname.clone(),
+        DnsRecord::new_txt("old.example.com", "moved").unwrap(),
+        // Records below a DNAME's owner are ignored.
+        DnsRecord::new_a("www.old.example.com", "10.0.0.1").unwrap(),
+        DnsRecord::new_a("www.new.example.com", "10.0.0.2").unwrap(),
+        DnsRecord::new_ns("sub.new.example.com", "ns1.example.com").unwrap(),
+        DnsRecord::new_dname("sub.new.example.com", "example.net").unwrap(),
+    ];
+    let lookup = |name: &str, typ: DnsType| query(name, typ).lookup_response(&records).unwrap();
+    // Synthesizes a CNAME with the DNAME's TTL.
+    // Does not follow it, even though its target has an A record in the zone.
+    for typ in [DnsType::A, DnsType::ANY, DnsType::CNAME] {
+        let response = lookup("WWW.Old.example.com", typ);
+        assert_eq!(DnsResponseCode::NoError, response.header.response_code);
+        assert!(response.header.authoritative_answer);
+        assert_eq!(
+            vec![
+                dname.clone(),
+                DnsRecord::new_cname("WWW.Old.example.com", "WWW.new.example.com")
+                    .unwrap()
+                    .with_ttl(3600)
+            ],
+            response.answers
+        );
+        assert_eq!(2, response.header.answer_count);
+    }
+    let response = lookup("a.b.old.example.com", DnsType::DNAME);
+    assert_eq!(
+        vec![
+            dname.clone(),
+            DnsRecord::new_cname("a.b.old.example.com", "a.b.new.example.com")
+                .unwrap()
+                .with_ttl(3600)
+        ],
+        response.answers
+    );
+    // Does not redirect the owner.
+    let response = lookup("old.example.com", DnsType::DNAME);
+    assert_eq!(vec![dname.clone()], response.answers);
+    let response = lookup("old.example.com", DnsType::TXT);
+    assert_eq!(&records[2..3], response.answers.as_slice());
+    let response = lookup("old.example.com", DnsType::A);
+    assert_eq!(DnsResponseCode::NoError, response.header.response_code);
+    assert!(response.answers.is_empty());
+    assert_eq!(vec![soa.with_ttl(300)], response.name_servers);
+    let response = lookup("www.new.example.com", DnsType::A);
+    assert_eq!(&records[4..5], response.answers.as_slice());
+    // A zone cut at the DNAME's owner takes precedence.
+    let response = lookup("www.sub.new.example.com", DnsType::A);
+    assert!(!response.header.authoritative_answer);
+    assert!(response.answers.is_empty());
+    assert_eq!(&records[5..6], response.name_servers.as_slice());
+    // Returns YXDOMAIN when the synthesized name is too long.
+    let long_label = "a".repeat(63);
+    let long_target = format!("{long_label}.{long_label}.{long_label}.example.com");
+    let records = [DnsRecord::new_dname("old.example.com", &long_target).unwrap()];
+    let response = query(&format!("{long_label}.old.example.com"), DnsType::A)
+        .lookup_response(&records)
+        .unwrap();
+    assert_eq!(DnsResponseCode::YxDomain, response.header.response_code);
+    assert_eq!(records.as_slice(), response.answers.as_slice());
+    // The synthesized name is 204 octets plus the query's first label.
+    // 49 bytes makes a name of 255 octets on the wire, the maximum.
+    let response = query(&format!("{}.old.example.com", "b".repeat(49)), DnsType::A)
+        .lookup_response(&records)
+        .unwrap();
+    assert_eq!(DnsResponseCode::NoError, response.header.response_code);
+    assert_eq!(2, response.answers.len());
+    match &response.answers[1] {
+        DnsRecord::CNAME { target, .. } => {
+            assert_eq!(255, target.as_bytes().unwrap().len());
+        }
+        _ => unreachable!(),
+    }
+    let mut out: FixedBuf<1024> = FixedBuf::new();
+    response.write(&mut out).unwrap();
+    // 50 bytes makes a name of 256 octets on the wire.
+    let response = query(&format!("{}.old.example.com", "b".repeat(50)), DnsType::A)
+        .lookup_response(&records)
+        .unwrap();
+    assert_eq!(DnsResponseCode::YxDomain, response.header.response_code);
+    assert_eq!(records.as_slice(), response.answers.as_slice());
+}
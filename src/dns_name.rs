@@ -1,7 +1,11 @@
-use crate::{read_u8, DnsError};
+use crate::dns_name_compressor::DnsNameCompressor;
+use crate::{read_position, write_u16_be, DnsError, DnsNameSyntax};
+use core::cmp::Ordering;
 use core::convert::TryFrom;
-use core::fmt::{Display, Formatter};
+use core::fmt::{Debug, Display, Formatter};
+use core::hash::{Hash, Hasher};
 use fixed_buffer::FixedBuf;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
 
 /// > 2.3.1. Preferred name syntax
 /// >
@@ -62,89 +66,276 @@ use fixed_buffer::FixedBuf;
 /// > - UDP messages: 512 octets or less
 ///
 /// <https://datatracker.ietf.org/doc/html/rfc1035#section-2.3.4>
-#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
-pub struct DnsName(String);
+///
+/// > Note that while it might be nice to have the labels of a domain name be restricted to
+/// > printable ASCII, ... any binary string whatever can be used as the label of any resource
+/// > record.
+///
+/// <https://datatracker.ietf.org/doc/html/rfc2181#section-11>
+///
+/// The name is stored as a sequence of labels, each prefixed by its length, without the
+/// terminating zero-length root label.  This can hold any name that can appear on the wire.
+/// Display and [`DnsName::new`] use RFC 1035 presentation format, with `\.` and `\DDD` escapes:
+///
+/// > `\X` where X is any character other than a digit (0-9), is used to quote that character so
+/// > that its special meaning does not apply.  For example, "\." can be used to place a dot
+/// > character in a label.
+/// >
+/// > `\DDD` where each D is a digit is the octet corresponding to the decimal number described
+/// > by DDD.  The resulting octet is assumed to be text and is not checked for special meaning.
+///
+/// <https://datatracker.ietf.org/doc/html/rfc1035#section-5.1>
+///
+/// Names sort in canonical order:
+///
+/// > For the purposes of DNS security, owner names are ordered by treating individual labels as
+/// > unsigned left-justified octet strings.  The absence of a octet sorts before a zero value
+/// > octet, and uppercase US-ASCII letters are treated as if they were lowercase US-ASCII
+/// > letters.
+/// >
+/// > To compute the canonical ordering of a set of DNS names, start by sorting the names
+/// > according to their most significant (rightmost) labels.  For names in which the most
+/// > significant label is identical, continue sorting according to their next most significant
+/// > label, and so forth.
+///
+/// <https://datatracker.ietf.org/doc/html/rfc4034#section-6.1>
+///
+/// Names keep the case of their letters, but comparisons ignore it:
+///
+/// > By convention, domain names can be stored with arbitrary case, but domain name comparisons
+/// > for all present domain functions are done in a case-insensitive manner, assuming an ASCII
+/// > character set, and a high order zero bit.  This means that you are free to create a node
+/// > with label "A" or a node with label "a", but not both as brothers; you could refer to
+/// > either using "a" or "A".
+///
+/// <https://datatracker.ietf.org/doc/html/rfc1035#section-2.3.3>
+///
+/// Keeping the case lets responses echo the question name byte-for-byte, which resolvers that
+/// randomize the case of their queries (DNS 0x20) require.
+#[derive(Clone)]
+pub struct DnsName(Vec<u8>);
 impl DnsName {
-    fn is_letter(b: u8) -> bool {
-        b.is_ascii_lowercase() || b.is_ascii_uppercase()
+    /// Names are at most 255 octets long on the wire, including the zero octet of the root
+    /// label.  The stored form leaves out that octet, so it is at most 254 octets.
+    const MAX_LEN: usize = 254;
+
+    /// Splits presentation format `value` into labels and removes escapes.
+    /// Returns no labels for the root name, `.`.
+    fn parse_labels(value: &str) -> Option<Vec<Vec<u8>>> {
+        if !value.is_ascii() {
+            return None;
+        }
+        if value == "." {
+            return Some(Vec::new());
+        }
+        let mut labels: Vec<Vec<u8>> = Vec::new();
+        let mut label: Vec<u8> = Vec::new();
+        let mut bytes = value.bytes();
+        while let Some(b) = bytes.next() {
+            match b {
+                b'.' => labels.push(std::mem::take(&mut label)),
+                b'\\' => {
+                    let escaped = bytes.next()?;
+                    if escaped.is_ascii_digit() {
+                        let mut n = u16::from(escaped - b'0');
+                        for _ in 0..2 {
+                            let digit = bytes.next().filter(u8::is_ascii_digit)?;
+                            n = n * 10 + u16::from(digit - b'0');
+                        }
+                        label.push(u8::try_from(n).ok()?);
+                    } else {
+                        label.push(escaped);
+                    }
+                }
+                other => label.push(other),
+            }
+        }
+        let trailing_dot = label.is_empty() && !labels.is_empty();
+        if !trailing_dot {
+            labels.push(label);
+        }
+        Some(labels)
     }
 
-    fn is_letter_digit(b: u8) -> bool {
-        Self::is_letter(b) || b.is_ascii_digit()
+    /// Makes a name that follows [`DnsNameSyntax::Service`] rules.
+    /// These accept host names and underscore labels like `_acme-challenge`.
+    ///
+    /// # Errors
+    /// Returns an error when `value` is not a valid DNS name.
+    pub fn new(value: &str) -> Result<Self, String> {
+        Self::with_syntax(value, DnsNameSyntax::Service)
     }
 
-    fn is_letter_digit_hyphen(b: u8) -> bool {
-        Self::is_letter_digit(b) || b == b'-'
+    /// Makes a name that follows [`DnsNameSyntax::Hostname`] rules.
+    ///
+    /// # Errors
+    /// Returns an error when `value` is not a valid host name.
+    pub fn new_hostname(value: &str) -> Result<Self, String> {
+        Self::with_syntax(value, DnsNameSyntax::Hostname)
     }
 
-    fn is_valid_label(label: &str) -> bool {
-        if label.is_empty() || label.len() > 63 {
-            return false;
-        }
-        let bytes = label.as_bytes();
-        Self::is_letter(bytes[0])
-            && bytes.iter().copied().all(Self::is_letter_digit_hyphen)
-            && Self::is_letter_digit(*bytes.last().unwrap())
+    /// Makes a name from an internationalized domain name.
+    /// Applies [UTS #46](https://www.unicode.org/reports/tr46/) mapping and converts non-ASCII
+    /// labels to Punycode A-labels, like `xn--bcher-kva`.
+    ///
+    /// > An "A-label" is the ASCII-Compatible Encoding (ACE, see Section 2.3.2.5) form of an IDNA-
+    /// > valid string.  It must be a complete label: IDNA is defined for labels, not for parts of
+    /// > them and not for complete domain names.
+    ///
+    /// <https://datatracker.ietf.org/doc/html/rfc5890#section-2.3.2.1>
+    ///
+    /// # Errors
+    /// Returns an error when `value` is not a valid internationalized domain name.
+    #[cfg(feature = "idna")]
+    pub fn from_unicode(value: &str) -> Result<Self, String> {
+        let ascii = idna::domain_to_ascii(value)
+            .map_err(|e| format!("not a valid internationalized DNS name: {value:?}: {e}"))?;
+        Self::new(&ascii)
     }
 
-    fn is_valid_name(value: &str) -> bool {
-        if !value.is_ascii() {
-            return false;
+    /// Returns the name with A-labels converted to Unicode, for display.
+    /// Returns the ASCII name when it contains an invalid A-label.
+    #[cfg(feature = "idna")]
+    #[must_use]
+    pub fn to_unicode(&self) -> String {
+        let ascii = self.to_string();
+        match idna::domain_to_unicode(&ascii) {
+            (unicode, Ok(())) => unicode,
+            (_, Err(_)) => ascii,
         }
-        value.split('.').all(Self::is_valid_label)
     }
 
+    /// Makes a name from presentation format `value`.
+    /// Each label must follow `syntax`, except a leftmost `*` wildcard label.
+    ///
     /// # Errors
-    /// Returns an error when `value` is not a valid DNS name.
-    pub fn new(value: &str) -> Result<Self, String> {
-        let trimmed = value.strip_suffix('.').unwrap_or(value);
-        if trimmed.len() > 255 || !Self::is_valid_name(trimmed) {
-            return Err(format!("not a valid DNS name: {value:?}"));
+    /// Returns an error when `value` is not a valid DNS name with labels that follow `syntax`.
+    pub fn with_syntax(value: &str, syntax: DnsNameSyntax) -> Result<Self, String> {
+        let err = || format!("not a valid DNS name: {value:?}");
+        let labels = Self::parse_labels(value).ok_or_else(err)?;
+        // A wildcard label may appear only as the leftmost label.
+        let skip = usize::from(labels.first().is_some_and(|label| label == b"*"));
+        if !labels
+            .iter()
+            .skip(skip)
+            .all(|label| syntax.is_valid_label(label))
+        {
+            return Err(err());
+        }
+        let mut bytes = Vec::with_capacity(value.len() + 1);
+        for label in labels {
+            let len = u8::try_from(label.len()).map_err(|_| err())?;
+            bytes.push(len);
+            bytes.extend(label);
+        }
+        if bytes.len() > Self::MAX_LEN {
+            return Err(err());
         }
-        Ok(Self(trimmed.to_ascii_lowercase()))
+        Ok(Self(bytes))
     }
 
+    /// Reads a name and follows any compression pointers in it.
+    ///
+    /// > 4.1.4. Message compression
+    /// >
+    /// > In order to reduce the size of messages, the domain system utilizes a compression scheme
+    /// > which eliminates the repetition of domain names in a message.  In this scheme, an entire
+    /// > domain name or a list of labels at the end of a domain name is replaced with a pointer to
+    /// > a prior occurance of the same name.
+    /// >
+    /// > The pointer takes the form of a two octet sequence:
+    /// >
+    /// > ```text
+    /// >     +--+--+--+--+--+--+--+--+--+--+--+--+--+--+--+--+
+    /// >     | 1  1|                OFFSET                   |
+    /// >     +--+--+--+--+--+--+--+--+--+--+--+--+--+--+--+--+
+    /// > ```
+    /// >
+    /// > The first two bits are ones.  This allows a pointer to be distinguished from a label,
+    /// > since the label must begin with two zero bits because labels are restricted to 63 octets
+    /// > or less.  (The 10 and 01 combinations are reserved for future use.)  The OFFSET field
+    /// > specifies an offset from the start of the message (i.e., the first octet of the ID field
+    /// > in the domain header).  A zero offset specifies the first byte of the ID field, etc.
+    ///
+    /// <https://datatracker.ietf.org/doc/html/rfc1035#section-4.1.4>
+    ///
+    /// Pointer offsets are relative to the start of `buf`'s memory, so the message must start
+    /// there.  Every pointer must point before the target of the previous pointer.
+    /// This rejects forward pointers and loops.
+    ///
     /// # Errors
     /// Returns an error when `buf` does not contain a valid name.
     pub fn read<const N: usize>(buf: &mut FixedBuf<N>) -> Result<DnsName, DnsError> {
-        let mut value = String::new();
-        for _ in 0..63 {
-            let len = read_u8(buf)? as usize;
-            if len == 0 {
-                if value.len() > 255 {
-                    return Err(DnsError::NameTooLong);
+        let start = read_position(buf);
+        let mem = &buf.mem()[..start + buf.len()];
+        let mut bytes: Vec<u8> = Vec::new();
+        let mut pos = start;
+        let mut limit = start;
+        let mut consumed: Option<usize> = None;
+        loop {
+            let len = *mem.get(pos).ok_or(DnsError::Truncated)?;
+            match len & 0xC0 {
+                0x00 => {}
+                0xC0 => {
+                    let low = *mem.get(pos + 1).ok_or(DnsError::Truncated)?;
+                    let target = (usize::from(len & 0x3F) << 8) | usize::from(low);
+                    if target >= limit {
+                        return Err(DnsError::InvalidPointer);
+                    }
+                    consumed.get_or_insert_with(|| pos + 2 - start);
+                    limit = target;
+                    pos = target;
+                    continue;
                 }
-                return Ok(Self(value));
+                _ => return Err(DnsError::InvalidLabel),
             }
-            if buf.readable().len() < len {
-                return Err(DnsError::Truncated);
+            let label_len = usize::from(len);
+            pos += 1;
+            if label_len == 0 {
+                break;
             }
-            let label_bytes = buf.read_bytes(len);
-            let label = std::str::from_utf8(label_bytes).map_err(|_| DnsError::InvalidLabel)?;
-            if !Self::is_valid_label(label) {
+            let label = mem.get(pos..pos + label_len).ok_or(DnsError::Truncated)?;
+            if !DnsNameSyntax::Any.is_valid_label(label) {
                 return Err(DnsError::InvalidLabel);
             }
-            if !value.is_empty() {
-                value.push('.');
+            bytes.push(len);
+            bytes.extend_from_slice(label);
+            if bytes.len() > Self::MAX_LEN {
+                return Err(DnsError::NameTooLong);
             }
-            value.push_str(label);
+            pos += label_len;
         }
-        Err(DnsError::TooManyLabels)
+        let consumed = consumed.unwrap_or_else(|| pos - start);
+        buf.read_bytes(consumed);
+        Ok(Self(bytes))
     }
 
     /// # Errors
     /// Returns an error when `buf` fills up.
     pub fn write<const N: usize>(&self, out: &mut FixedBuf<N>) -> Result<(), DnsError> {
-        for label in self.0.split('.') {
-            if label.len() > 63 {
-                return Err(DnsError::Unreachable(file!(), line!()));
+        self.write_compressed(out, &mut DnsNameCompressor::disabled())
+    }
+
+    /// Writes the name.  When `compressor` has seen a suffix of the name,
+    /// writes a pointer to it in place of the suffix's labels.
+    ///
+    /// # Errors
+    /// Returns an error when `buf` fills up.
+    pub(crate) fn write_compressed<const N: usize>(
+        &self,
+        out: &mut FixedBuf<N>,
+        compressor: &mut DnsNameCompressor,
+    ) -> Result<(), DnsError> {
+        let mut rest: &[u8] = &self.0;
+        while let Some(len) = rest.first() {
+            if let Some(offset) = compressor.get(rest) {
+                return write_u16_be(out, 0xC000 | offset);
             }
-            let len =
-                u8::try_from(label.len()).map_err(|_| DnsError::Unreachable(file!(), line!()))?;
-            out.write_bytes(&[len])
-                .map_err(|_| DnsError::ResponseBufferFull)?;
-            out.write_bytes(label.as_bytes())
+            compressor.insert(rest, out);
+            let (label, tail) = rest.split_at(1 + usize::from(*len));
+            out.write_bytes(label)
                 .map_err(|_| DnsError::ResponseBufferFull)?;
+            rest = tail;
         }
         out.write_bytes(&[0])
             .map_err(|_| DnsError::ResponseBufferFull)?;
@@ -152,21 +343,423 @@ impl DnsName {
     }
 
     /// # Errors
-    /// Returns an error when the name is longer than 255 bytes.  This cannot happen.
+    /// Returns an error when the name does not fit in 256 bytes.  This cannot happen, because
+    /// names are at most 255 bytes on the wire.
     pub fn as_bytes(&self) -> Result<FixedBuf<256>, DnsError> {
         let mut buf: FixedBuf<256> = FixedBuf::new();
         self.write(&mut buf)?;
         Ok(buf)
     }
 
+    /// Returns the name in presentation format.
+    ///
+    /// This returned `&str` before names were stored in wire format.
+    #[deprecated(note = "use to_string()")]
+    #[must_use]
+    pub fn inner(&self) -> String {
+        self.to_string()
+    }
+
+    /// Returns true when the leftmost label is `*`.
+    ///
+    /// > A "wildcard domain name" is defined by having its initial (i.e., leftmost or least
+    /// > significant) label be, in binary format: `0000 0001 0010 1010` (binary) = `0x01 0x2a` (hex)
+    ///
+    /// <https://datatracker.ietf.org/doc/html/rfc4592#section-2.1.1>
+    #[must_use]
+    pub fn is_wildcard(&self) -> bool {
+        self.0.starts_with(&[1, b'*'])
+    }
+
+    /// The root name, `.`, which has no labels.
     #[must_use]
-    pub fn inner(&self) -> &str {
-        &self.0
+    pub const fn root() -> Self {
+        Self(Vec::new())
+    }
+
+    /// Returns true when this is the root name.
+    #[must_use]
+    pub fn is_root(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the labels, leftmost first.  Does not include the empty root label.
+    pub fn labels(&self) -> impl Iterator<Item = &[u8]> {
+        let mut rest: &[u8] = &self.0;
+        std::iter::from_fn(move || {
+            let (len, tail) = rest.split_first()?;
+            let (label, tail) = tail.split_at(usize::from(*len));
+            rest = tail;
+            Some(label)
+        })
+    }
+
+    /// Returns the number of labels, not counting the empty root label.
+    #[must_use]
+    pub fn num_labels(&self) -> usize {
+        self.labels().count()
+    }
+
+    /// Returns the name with the leftmost label removed,
+    /// or `None` when this is the root name.
+    #[must_use]
+    pub fn parent(&self) -> Option<DnsName> {
+        let len = self.0.first()?;
+        Some(Self(self.0[1 + usize::from(*len)..].to_vec()))
+    }
+
+    /// Returns true when this name equals `other` or is below it.
+    /// Every name is a subdomain of the root name.
+    #[must_use]
+    pub fn is_subdomain_of(&self, other: &DnsName) -> bool {
+        self.suffix_offset(other).is_some()
+    }
+
+    /// Returns the offset in `self.0` of the labels that equal `other`.
+    fn suffix_offset(&self, other: &DnsName) -> Option<usize> {
+        let mut offset = 0;
+        loop {
+            let rest = &self.0[offset..];
+            if rest.eq_ignore_ascii_case(&other.0) {
+                return Some(offset);
+            }
+            offset += 1 + usize::from(*rest.first()?);
+        }
+    }
+
+    /// Returns the labels to the left of `zone`, like `www` for `www.example.com` in zone
+    /// `example.com`.  Returns the root name when this name equals `zone`.
+    /// Returns `None` when this name is not a subdomain of `zone`.
+    #[must_use]
+    pub fn strip_suffix(&self, zone: &DnsName) -> Option<DnsName> {
+        let offset = self.suffix_offset(zone)?;
+        Some(Self(self.0[..offset].to_vec()))
+    }
+
+    /// Returns the name with `label` added to the left.
+    ///
+    /// # Errors
+    /// Returns `InvalidLabel` when `label` is empty or longer than 63 bytes.
+    /// Returns `NameTooLong` when the result is longer than 255 bytes.
+    pub fn prepend_label(&self, label: &[u8]) -> Result<DnsName, DnsError> {
+        if !DnsNameSyntax::Any.is_valid_label(label) {
+            return Err(DnsError::InvalidLabel);
+        }
+        let len = u8::try_from(label.len()).map_err(|_| DnsError::InvalidLabel)?;
+        let mut bytes = Vec::with_capacity(1 + label.len() + self.0.len());
+        bytes.push(len);
+        bytes.extend_from_slice(label);
+        bytes.extend_from_slice(&self.0);
+        if bytes.len() > Self::MAX_LEN {
+            return Err(DnsError::NameTooLong);
+        }
+        Ok(Self(bytes))
+    }
+
+    /// Returns the name with the labels of `origin` added to the right,
+    /// like `www.example.com` for `www` and origin `example.com`.
+    ///
+    /// # Errors
+    /// Returns `NameTooLong` when the result is longer than 255 bytes.
+    pub fn append(&self, origin: &DnsName) -> Result<DnsName, DnsError> {
+        let mut bytes = Vec::with_capacity(self.0.len() + origin.0.len());
+        bytes.extend_from_slice(&self.0);
+        bytes.extend_from_slice(&origin.0);
+        if bytes.len() > Self::MAX_LEN {
+            return Err(DnsError::NameTooLong);
+        }
+        Ok(Self(bytes))
+    }
+
+    /// Returns the reverse-lookup name of `addr`, like `4.3.2.1.in-addr.arpa` for `1.2.3.4`.
+    ///
+    /// > The Internet uses a special domain to support gateway location and Internet address to
+    /// > host mapping. ... The domain begins at IN-ADDR.ARPA and has a substructure which follows
+    /// > the Internet addressing structure.
+    ///
+    /// <https://datatracker.ietf.org/doc/html/rfc1035#section-3.5>
+    ///
+    /// > An IPv6 address is represented as a name in the IP6.ARPA domain by a sequence of nibbles
+    /// > separated by dots with the suffix ".IP6.ARPA".  The sequence of nibbles is encoded in
+    /// > reverse order, i.e., the low-order nibble is encoded first, followed by the next low-order
+    /// > nibble and so on.  Each nibble is represented by a hexadecimal digit.
+    ///
+    /// <https://datatracker.ietf.org/doc/html/rfc3596#section-2.5>
+    #[must_use]
+    pub fn from_ip(addr: IpAddr) -> Self {
+        match addr {
+            IpAddr::V4(addr) => Self::reverse_v4(addr, 32),
+            IpAddr::V6(addr) => Self::reverse_v6(addr, 128),
+        }
+    }
+
+    /// Returns the name of the reverse-lookup zone for the network `addr`/`prefix_len`,
+    /// like `2.0.192.in-addr.arpa` for `192.0.2.0/24`.
+    ///
+    /// IPv4 prefixes from 25 through 31 bits use RFC 2317 classless delegation names,
+    /// like `64/26.2.0.192.in-addr.arpa` for `192.0.2.64/26`.
+    ///
+    /// > ... we can use the above-mentioned CNAME records to delegate part of the in-addr.arpa
+    /// > space.  ... The "/" character is used here to indicate the prefix length.
+    ///
+    /// <https://datatracker.ietf.org/doc/html/rfc2317#section-4>
+    ///
+    /// Ignores the host bits of `addr`.
+    ///
+    /// # Errors
+    /// Returns an error when `prefix_len` is longer than the address,
+    /// is not a multiple of 8 for IPv4 prefixes shorter than 25 bits,
+    /// or is not a multiple of 4 for IPv6.
+    pub fn from_ip_prefix(addr: IpAddr, prefix_len: u8) -> Result<Self, String> {
+        match addr {
+            IpAddr::V4(v4)
+                if prefix_len <= 32 && (prefix_len.is_multiple_of(8) || prefix_len > 24) =>
+            {
+                Ok(Self::reverse_v4(v4, prefix_len))
+            }
+            IpAddr::V6(v6) if prefix_len <= 128 && prefix_len.is_multiple_of(4) => {
+                Ok(Self::reverse_v6(v6, prefix_len))
+            }
+            _ => Err(format!(
+                "cannot make a reverse-lookup name for prefix {addr}/{prefix_len}"
+            )),
+        }
+    }
+
+    /// Returns the number of digits in `value` written in decimal.
+    const fn decimal_len(value: u8) -> u8 {
+        match value {
+            0..=9 => 1,
+            10..=99 => 2,
+            _ => 3,
+        }
+    }
+
+    // The longest result is `254/31.255.255.255.in-addr.arpa`, 32 octets stored,
+    // so it always fits in `MAX_LEN`.
+    fn reverse_v4(addr: Ipv4Addr, prefix_len: u8) -> Self {
+        let octets = addr.octets();
+        let mut bytes = Vec::new();
+        if !prefix_len.is_multiple_of(8) {
+            let first = octets[3] & (u8::MAX << (32 - prefix_len));
+            bytes.push(Self::decimal_len(first) + 1 + Self::decimal_len(prefix_len));
+            bytes.extend_from_slice(format!("{first}/{prefix_len}").as_bytes());
+        }
+        let num_octets = usize::from(prefix_len / 8);
+        for octet in octets[..num_octets].iter().rev() {
+            bytes.push(Self::decimal_len(*octet));
+            bytes.extend_from_slice(octet.to_string().as_bytes());
+        }
+        bytes.extend_from_slice(b"\x07in-addr\x04arpa");
+        Self(bytes)
+    }
+
+    // The longest result has 32 one-nibble labels and `ip6.arpa`, 73 octets stored,
+    // so it always fits in `MAX_LEN`.
+    fn reverse_v6(addr: Ipv6Addr, prefix_len: u8) -> Self {
+        const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+        let nibbles: Vec<u8> = addr
+            .octets()
+            .iter()
+            .flat_map(|octet| [octet >> 4, octet & 0xF])
+            .collect();
+        let num_nibbles = usize::from(prefix_len / 4);
+        let mut bytes = Vec::new();
+        for nibble in nibbles[..num_nibbles].iter().rev() {
+            bytes.extend_from_slice(&[1, HEX_DIGITS[usize::from(*nibble)]]);
+        }
+        bytes.extend_from_slice(b"\x03ip6\x04arpa");
+        Self(bytes)
+    }
+
+    /// Parses a decimal number without leading zeros.
+    fn parse_decimal(label: &[u8]) -> Option<u8> {
+        if label.is_empty() || label.len() > 3 || (label.len() > 1 && label[0] == b'0') {
+            return None;
+        }
+        std::str::from_utf8(label).ok()?.parse().ok()
+    }
+
+    /// Parses an RFC 2317 label like `64/26` into the network's last octet and prefix length.
+    fn parse_classless_label(label: &[u8]) -> Option<(u8, u8)> {
+        let slash = label.iter().position(|b| *b == b'/')?;
+        let net = Self::parse_decimal(&label[..slash])?;
+        let prefix_len = Self::parse_decimal(&label[slash + 1..])?;
+        if !(25..=31).contains(&prefix_len) || net & !(u8::MAX << (32 - prefix_len)) != 0 {
+            return None;
+        }
+        Some((net, prefix_len))
+    }
+
+    /// Splits the name into the labels below `in-addr.arpa` or `ip6.arpa`.
+    /// Returns `None` when the name is not in one of those zones.
+    fn reverse_labels(&self) -> Option<(bool, Vec<&[u8]>)> {
+        let mut labels: Vec<&[u8]> = self.labels().collect();
+        let arpa = labels.pop()?;
+        let zone = labels.pop()?;
+        if !arpa.eq_ignore_ascii_case(b"arpa") {
+            return None;
+        }
+        if zone.eq_ignore_ascii_case(b"in-addr") {
+            Some((true, labels))
+        } else if zone.eq_ignore_ascii_case(b"ip6") {
+            Some((false, labels))
+        } else {
+            None
+        }
+    }
+
+    fn parse_nibbles(labels: &[&[u8]]) -> Option<[u8; 16]> {
+        let mut octets = [0_u8; 16];
+        for (n, label) in labels.iter().rev().enumerate() {
+            let nibble = match label {
+                [b] => char::from(*b).to_digit(16)?,
+                _ => return None,
+            };
+            let nibble = u8::try_from(nibble).ok()?;
+            octets[n / 2] |= if n % 2 == 0 { nibble << 4 } else { nibble };
+        }
+        Some(octets)
+    }
+
+    /// Returns the address of a reverse-lookup name, like `1.2.3.4` for `4.3.2.1.in-addr.arpa`.
+    /// Also accepts names in RFC 2317 classless delegation zones,
+    /// like `65.64/26.2.0.192.in-addr.arpa`.
+    ///
+    /// Returns `None` when the name is not a reverse-lookup name for a whole address.
+    #[must_use]
+    pub fn to_ip(&self) -> Option<IpAddr> {
+        match self.reverse_labels()? {
+            (true, labels) => {
+                let (host, network) = match labels.as_slice() {
+                    [host, classless, network @ ..] if network.len() == 3 => {
+                        let host = Self::parse_decimal(host)?;
+                        let (net, prefix_len) = Self::parse_classless_label(classless)?;
+                        if host & (u8::MAX << (32 - prefix_len)) != net {
+                            return None;
+                        }
+                        (host, network)
+                    }
+                    [host, network @ ..] if network.len() == 3 => {
+                        (Self::parse_decimal(host)?, network)
+                    }
+                    _ => return None,
+                };
+                let c = Self::parse_decimal(network[0])?;
+                let b = Self::parse_decimal(network[1])?;
+                let a = Self::parse_decimal(network[2])?;
+                Some(IpAddr::V4(Ipv4Addr::new(a, b, c, host)))
+            }
+            (false, labels) if labels.len() == 32 => {
+                Some(IpAddr::V6(Ipv6Addr::from(Self::parse_nibbles(&labels)?)))
+            }
+            (false, _) => None,
+        }
+    }
+
+    /// Returns the network of a reverse-lookup zone name and its prefix length,
+    /// like `(192.0.2.0, 24)` for `2.0.192.in-addr.arpa`
+    /// and `(192.0.2.64, 26)` for `64/26.2.0.192.in-addr.arpa`.
+    ///
+    /// Returns `None` when the name is not a reverse-lookup name.
+    #[must_use]
+    pub fn to_ip_prefix(&self) -> Option<(IpAddr, u8)> {
+        match self.reverse_labels()? {
+            (true, labels) => {
+                let (last, network, mut prefix_len) = match labels.as_slice() {
+                    [classless, network @ ..] if network.len() == 3 => {
+                        match Self::parse_classless_label(classless) {
+                            Some((net, prefix_len)) => (Some(net), network, prefix_len),
+                            None => (None, labels.as_slice(), 0),
+                        }
+                    }
+                    network if network.len() <= 4 => (None, network, 0),
+                    _ => return None,
+                };
+                let mut octets = [0_u8; 4];
+                for (n, label) in network.iter().rev().enumerate() {
+                    octets[n] = Self::parse_decimal(label)?;
+                }
+                if let Some(net) = last {
+                    octets[3] = net;
+                } else {
+                    prefix_len = u8::try_from(8 * network.len()).ok()?;
+                }
+                Some((IpAddr::V4(Ipv4Addr::from(octets)), prefix_len))
+            }
+            (false, labels) if labels.len() <= 32 => {
+                let octets = Self::parse_nibbles(&labels)?;
+                let prefix_len = u8::try_from(4 * labels.len()).ok()?;
+                Some((IpAddr::V6(Ipv6Addr::from(octets)), prefix_len))
+            }
+            (false, _) => None,
+        }
+    }
+
+    /// Returns the wildcard name directly below this name,
+    /// or `None` when it would be too long.
+    pub(crate) fn wildcard_child(&self) -> Option<DnsName> {
+        self.prepend_label(b"*").ok()
     }
 }
 impl Display for DnsName {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
-        write!(f, "{}", self.0)
+        if self.is_root() {
+            return write!(f, ".");
+        }
+        for (n, label) in self.labels().enumerate() {
+            if n > 0 {
+                write!(f, ".")?;
+            }
+            for b in label.iter().copied() {
+                match b {
+                    b'.' | b'\\' | b'"' | b'(' | b')' | b';' | b'@' | b'$' => {
+                        write!(f, "\\{}", char::from(b))?;
+                    }
+                    0x21..=0x7E => write!(f, "{}", char::from(b))?,
+                    _ => write!(f, "\\{b:03}")?,
+                }
+            }
+        }
+        Ok(())
+    }
+}
+impl Ord for DnsName {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let labels: Vec<&[u8]> = self.labels().collect();
+        let other_labels: Vec<&[u8]> = other.labels().collect();
+        for (label, other_label) in labels.iter().rev().zip(other_labels.iter().rev()) {
+            let ordering = label
+                .iter()
+                .map(u8::to_ascii_lowercase)
+                .cmp(other_label.iter().map(u8::to_ascii_lowercase));
+            if ordering != Ordering::Equal {
+                return ordering;
+            }
+        }
+        labels.len().cmp(&other_labels.len())
+    }
+}
+impl PartialEq for DnsName {
+    fn eq(&self, other: &Self) -> bool {
+        // Length octets are below 64, so lowercasing leaves them unchanged.
+        self.0.eq_ignore_ascii_case(&other.0)
+    }
+}
+impl Eq for DnsName {}
+impl Hash for DnsName {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.to_ascii_lowercase().hash(state);
+    }
+}
+impl PartialOrd for DnsName {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl Debug for DnsName {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        write!(f, "DnsName({:?})", self.to_string())
     }
 }
 impl std::convert::TryFrom<&'static str> for DnsName {
@@ -189,8 +782,15 @@ fn test_err() {
 #[cfg(test)]
 #[test]
 fn test_new_label_separators() {
-    DnsName::new(".").unwrap_err();
-    assert_eq!("a", DnsName::new("a.").unwrap().inner());
+    assert_eq!(DnsName::root(), DnsName::new(".").unwrap());
+    assert_eq!(".", DnsName::new(".").unwrap().to_string());
+    assert_eq!(
+        Ok(DnsName::root()),
+        DnsName::with_syntax(".", DnsNameSyntax::Any)
+    );
+    DnsName::new("..").unwrap_err();
+    DnsName::new("").unwrap_err();
+    assert_eq!("a", DnsName::new("a.").unwrap().to_string());
     DnsName::new("a..").unwrap_err();
     DnsName::new(".a").unwrap_err();
     DnsName::new("b..a").unwrap_err();
@@ -207,7 +807,8 @@ fn test_new_label_charset() {
     }
     for b in 0..=255_u8 {
         let c = char::from(b);
-        if !ALLOWED.contains(c) {
+        // `\` starts an escape sequence.
+        if !ALLOWED.contains(c) && c != '\\' {
             let value = format!("a{c}a");
             assert_eq!(
                 <Result<DnsName, String>>::Err(format!("not a valid DNS name: {value:?}")),
@@ -226,17 +827,28 @@ fn test_new_label_charset() {
 fn test_new_label_normalizing() {
     assert_eq!(
         "abcdefghijklmnopqrstuvwxyz",
-        DnsName::new("abcdefghijklmnopqrstuvwxyz").unwrap().inner()
+        DnsName::new("abcdefghijklmnopqrstuvwxyz")
+            .unwrap()
+            .to_string()
     );
     assert_eq!(
-        "abcdefghijklmnopqrstuvwxyz",
-        DnsName::new("ABCDEFGHIJKLMNOPQRSTUVWXYZ").unwrap().inner()
+        "ABCDEFGHIJKLMNOPQRSTUVWXYZ",
+        DnsName::new("ABCDEFGHIJKLMNOPQRSTUVWXYZ")
+            .unwrap()
+            .to_string()
     );
-    assert_eq!("a0123456789", DnsName::new("a0123456789").unwrap().inner());
-    assert_eq!("a-b.c", DnsName::new("a-b.c").unwrap().inner());
     assert_eq!(
-        "xyz321-654abc.def",
-        DnsName::new("Xyz321-654abC.DeF").unwrap().inner()
+        DnsName::new("abcdefghijklmnopqrstuvwxyz").unwrap(),
+        DnsName::new("ABCDEFGHIJKLMNOPQRSTUVWXYZ").unwrap()
+    );
+    assert_eq!(
+        "a0123456789",
+        DnsName::new("a0123456789").unwrap().to_string()
+    );
+    assert_eq!("a-b.c", DnsName::new("a-b.c").unwrap().to_string());
+    assert_eq!(
+        "Xyz321-654abC.DeF",
+        DnsName::new("Xyz321-654abC.DeF").unwrap().to_string()
     );
 }
 
@@ -244,8 +856,13 @@ fn test_new_label_normalizing() {
 #[test]
 fn test_new_label_format() {
     DnsName::new("a").unwrap();
-    DnsName::new("1").unwrap_err();
-    DnsName::new("1a").unwrap_err();
+    DnsName::new("1").unwrap();
+    DnsName::new("1a").unwrap();
+    DnsName::with_syntax("1", DnsNameSyntax::Rfc1035).unwrap_err();
+    DnsName::with_syntax("1a", DnsNameSyntax::Rfc1035).unwrap_err();
+    DnsName::with_syntax("a1", DnsNameSyntax::Rfc1035).unwrap();
+    DnsName::new("1.2.3.4.in-addr.arpa").unwrap();
+    DnsName::new_hostname("3com.com").unwrap();
     DnsName::new("a1").unwrap();
     DnsName::new("a9876543210").unwrap();
     DnsName::new("-").unwrap_err();
@@ -270,63 +887,650 @@ fn test_new_label_length() {
 #[cfg(test)]
 #[test]
 fn test_new_name_length() {
-    DnsName::new(concat!(
-        "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa.",
-        "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa.",
-        "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa.",
-        "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"
-    ))
-    .unwrap();
-    DnsName::new(concat!(
+    // 254 octets, plus the root label's octet on the wire.
+    let max = format!("{0}.{0}.{0}.{1}", "a".repeat(63), "a".repeat(61));
+    let name = DnsName::new(&max).unwrap();
+    assert_eq!(254, name.0.len());
+    assert_eq!(255, name.as_bytes().unwrap().len());
+    DnsName::new(&format!("{max}.")).unwrap();
+    DnsName::new(&format!("{max}a")).unwrap_err();
+    DnsName::new(&format!("{max}.a")).unwrap_err();
+    let max = ["a"; 127].join(".");
+    assert_eq!(254, DnsName::new(&max).unwrap().0.len());
+    DnsName::new(&format!("{max}a")).unwrap_err();
+    DnsName::new(&format!("{max}.a")).unwrap_err();
+}
+
+#[cfg(test)]
+#[test]
+fn test_read_name_length() {
+    for (last_label_len, expected_len) in [(61_u8, Ok(254)), (62, Err(DnsError::NameTooLong))] {
+        let mut buf: FixedBuf<512> = FixedBuf::new();
+        for label_len in [63, 63, 63, last_label_len] {
+            buf.write_bytes(&[label_len]).unwrap();
+            buf.write_bytes(&vec![b'a'; usize::from(label_len)])
+                .unwrap();
+        }
+        buf.write_bytes(&[0]).unwrap();
+        assert_eq!(
+            expected_len,
+            DnsName::read(&mut buf).map(|name| name.0.len())
+        );
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_read() {
+    let mut buf: FixedBuf<16> = FixedBuf::new();
+    buf.write_bytes(b"\x01a\x03bcd\x00\xFF").unwrap();
+    assert_eq!("a.bcd", DnsName::read(&mut buf).unwrap().to_string());
+    assert_eq!(&[0xFF_u8], buf.readable());
+    for bytes in [&b""[..], b"\x01", b"\x02a", b"\x01a", b"\x01a\x01"] {
+        let mut buf: FixedBuf<16> = FixedBuf::new();
+        buf.write_bytes(bytes).unwrap();
+        assert_eq!(
+            Err(DnsError::Truncated),
+            DnsName::read(&mut buf),
+            "{bytes:?}"
+        );
+    }
+    let mut buf: FixedBuf<16> = FixedBuf::new();
+    buf.write_bytes(b"\x011\x03a b\x00").unwrap();
+    assert_eq!("1.a\\032b", DnsName::read(&mut buf).unwrap().to_string());
+    let mut buf: FixedBuf<16> = FixedBuf::new();
+    buf.write_bytes(b"\x01a\x01.\x00").unwrap();
+    assert_eq!("a.\\.", DnsName::read(&mut buf).unwrap().to_string());
+    let mut buf: FixedBuf<16> = FixedBuf::new();
+    buf.write_bytes(b"\x41a\x00").unwrap();
+    assert_eq!(Err(DnsError::InvalidLabel), DnsName::read(&mut buf));
+}
+
+#[cfg(test)]
+#[test]
+fn test_read_compressed() {
+    let mut buf: FixedBuf<32> = FixedBuf::new();
+    buf.write_bytes(b"\x01a\x03bcd\x00\x01x\xC0\x02\x01y\xC0\x07\xC0\x0B")
+        .unwrap();
+    assert_eq!("a.bcd", DnsName::read(&mut buf).unwrap().to_string());
+    assert_eq!("x.bcd", DnsName::read(&mut buf).unwrap().to_string());
+    assert_eq!("y.x.bcd", DnsName::read(&mut buf).unwrap().to_string());
+    assert_eq!("y.x.bcd", DnsName::read(&mut buf).unwrap().to_string());
+    assert!(buf.is_empty());
+}
+
+#[cfg(test)]
+#[test]
+fn test_read_bad_pointer() {
+    // Points to itself.
+    let mut buf: FixedBuf<16> = FixedBuf::new();
+    buf.write_bytes(b"\xC0\x00").unwrap();
+    assert_eq!(Err(DnsError::InvalidPointer), DnsName::read(&mut buf));
+    // Points forward.
+    let mut buf: FixedBuf<16> = FixedBuf::new();
+    buf.write_bytes(b"\xC0\x02\x01a\x00").unwrap();
+    assert_eq!(Err(DnsError::InvalidPointer), DnsName::read(&mut buf));
+    // Loops between two pointers.
+    let mut buf: FixedBuf<16> = FixedBuf::new();
+    buf.write_bytes(b"\x01a\xC0\x04\x01b\xC0\x00\x01c\xC0\x04")
+        .unwrap();
+    buf.read_bytes(8);
+    assert_eq!(Err(DnsError::InvalidPointer), DnsName::read(&mut buf));
+    // Label loops back to its own start.
+    let mut buf: FixedBuf<16> = FixedBuf::new();
+    buf.write_bytes(b"\x00\x01a\xC0\x01").unwrap();
+    buf.read_bytes(1);
+    assert_eq!(Err(DnsError::InvalidPointer), DnsName::read(&mut buf));
+    // Truncated pointer.
+    let mut buf: FixedBuf<16> = FixedBuf::new();
+    buf.write_bytes(b"\x00\xC0").unwrap();
+    buf.read_bytes(1);
+    assert_eq!(Err(DnsError::Truncated), DnsName::read(&mut buf));
+}
+
+#[cfg(test)]
+#[test]
+fn test_read_compressed_name_too_long() {
+    let mut buf: FixedBuf<512> = FixedBuf::new();
+    let label = [b'a'; 63];
+    let mut prev: Option<u16> = None;
+    for _ in 0..5 {
+        let start = buf.len();
+        buf.write_bytes(&[63]).unwrap();
+        buf.write_bytes(&label).unwrap();
+        match prev {
+            None => buf.write_bytes(&[0]).unwrap(),
+            Some(offset) => buf.write_bytes(&(0xC000 | offset).to_be_bytes()).unwrap(),
+        };
+        prev = Some(u16::try_from(start).unwrap());
+    }
+    let start = buf.len();
+    buf.write_bytes(&(0xC000 | prev.unwrap()).to_be_bytes())
+        .unwrap();
+    buf.read_bytes(start);
+    assert_eq!(Err(DnsError::NameTooLong), DnsName::read(&mut buf));
+}
+
+#[cfg(test)]
+#[test]
+fn test_syntax() {
+    assert_eq!(
+        "_acme-challenge.Example.com",
+        DnsName::new("_acme-challenge.Example.com")
+            .unwrap()
+            .to_string()
+    );
+    DnsName::new("_sip._tcp.example.com").unwrap();
+    DnsName::new("_dmarc.example.com").unwrap();
+    DnsName::new_hostname("www.example.com").unwrap();
+    DnsName::new_hostname("_acme-challenge.example.com").unwrap_err();
+    DnsName::with_syntax("_dmarc.example.com", DnsNameSyntax::Hostname).unwrap_err();
+    DnsName::new("a_b.example.com").unwrap_err();
+    let mut buf: FixedBuf<32> = FixedBuf::new();
+    buf.write_bytes(b"\x04_sip\x04_tcp\x01a\x00").unwrap();
+    assert_eq!("_sip._tcp.a", DnsName::read(&mut buf).unwrap().to_string());
+}
+
+#[cfg(test)]
+#[test]
+fn test_wildcard() {
+    assert!(DnsName::new("*.preview.example.com").unwrap().is_wildcard());
+    assert!(DnsName::new_hostname("*.example.com")
+        .unwrap()
+        .is_wildcard());
+    assert!(DnsName::new("*").unwrap().is_wildcard());
+    assert!(!DnsName::new("a.example.com").unwrap().is_wildcard());
+    DnsName::new("a.*.example.com").unwrap_err();
+    DnsName::new("*a.example.com").unwrap_err();
+    DnsName::new("**.example.com").unwrap_err();
+    assert_eq!(
+        DnsName::new("*.example.com").unwrap(),
+        DnsName::new("example.com")
+            .unwrap()
+            .wildcard_child()
+            .unwrap()
+    );
+}
+
+#[cfg(all(test, feature = "idna"))]
+#[test]
+fn test_unicode() {
+    assert_eq!(
+        "xn--bcher-kva.example",
+        DnsName::from_unicode("bücher.example").unwrap().to_string()
+    );
+    assert_eq!(
+        "xn--bcher-kva.example",
+        DnsName::from_unicode("BÜCHER.Example").unwrap().to_string()
+    );
+    assert_eq!(
+        "www.example.com",
+        DnsName::from_unicode("WWW.Example.com.")
+            .unwrap()
+            .to_string()
+    );
+    assert_eq!(
+        "_acme-challenge.xn--mnchen-3ya.de",
+        DnsName::from_unicode("_acme-challenge.münchen.de")
+            .unwrap()
+            .to_string()
+    );
+    // Ideographic full stop separates labels.
+    assert_eq!(
+        "xn--fiqs8s.xn--fiqz9s",
+        DnsName::from_unicode("中国\u{3002}中國")
+            .unwrap()
+            .to_string()
+    );
+    DnsName::from_unicode("a..b").unwrap_err();
+    DnsName::from_unicode("xn--a.example").unwrap_err();
+    assert_eq!(
+        "bücher.example",
+        DnsName::new("xn--bcher-kva.example").unwrap().to_unicode()
+    );
+    assert_eq!(
+        "www.example.com",
+        DnsName::new("www.example.com").unwrap().to_unicode()
+    );
+    assert_eq!(
+        "xn--a.example",
+        DnsName::new("xn--a.example").unwrap().to_unicode()
+    );
+}
+
+#[cfg(test)]
+#[test]
+fn test_escapes() {
+    assert_eq!(
+        "a\\.b.c",
+        DnsName::with_syntax("a\\.b.c", DnsNameSyntax::Any)
+            .unwrap()
+            .to_string()
+    );
+    assert_eq!(
+        2,
+        DnsName::with_syntax("a\\.b.c", DnsNameSyntax::Any)
+            .unwrap()
+            .labels()
+            .count()
+    );
+    assert_eq!(
+        "abc.def",
+        DnsName::new("\\097b\\c.d\\ef").unwrap().to_string()
+    );
+    assert_eq!(
+        "a\\032b\\\\\\000\\255\\\"\\;",
+        DnsName::with_syntax("a b\\\\\\000\\255\\\"\\;", DnsNameSyntax::Any)
+            .unwrap()
+            .to_string()
+    );
+    // Escapes cannot bypass syntax rules.
+    DnsName::new("a\\.b.c").unwrap_err();
+    DnsName::new("a\\032b").unwrap_err();
+    // Bad escapes.
+    for value in ["a\\", "a\\1", "a\\12", "a\\12x", "a\\256"] {
+        DnsName::with_syntax(value, DnsNameSyntax::Any).expect_err(value);
+    }
+    // Escaped trailing dot is part of the label.
+    assert_eq!(
+        1,
+        DnsName::with_syntax("a\\.", DnsNameSyntax::Any)
+            .unwrap()
+            .labels()
+            .count()
+    );
+}
+
+#[cfg(test)]
+#[test]
+fn test_binary_round_trip() {
+    let mut wire: Vec<u8> = vec![4, b'a', b'.', 0xFF, b' ', 3, 0, b'\\', b'"', 0];
+    let mut buf: FixedBuf<16> = FixedBuf::new();
+    buf.write_bytes(&wire).unwrap();
+    let name = DnsName::read(&mut buf).unwrap();
+    assert_eq!("a\\.\\255\\032.\\000\\\\\\\"", name.to_string());
+    let mut out: FixedBuf<16> = FixedBuf::new();
+    name.write(&mut out).unwrap();
+    assert_eq!(wire.as_slice(), out.readable());
+    let parsed = DnsName::with_syntax(&name.to_string(), DnsNameSyntax::Any).unwrap();
+    assert_eq!(name, parsed);
+    // Reading preserves case and non-ASCII bytes.
+    wire = vec![2, b'A', 0xC3, 0];
+    let mut buf: FixedBuf<16> = FixedBuf::new();
+    buf.write_bytes(&wire).unwrap();
+    assert_eq!("A\\195", DnsName::read(&mut buf).unwrap().to_string());
+    // The root name round-trips too.
+    let mut buf: FixedBuf<16> = FixedBuf::new();
+    buf.write_bytes(&[0]).unwrap();
+    let root = DnsName::read(&mut buf).unwrap();
+    assert_eq!(".", root.to_string());
+    assert_eq!(Ok(root), DnsName::with_syntax(".", DnsNameSyntax::Any));
+}
+
+#[cfg(test)]
+#[test]
+fn test_write() {
+    let mut out: FixedBuf<32> = FixedBuf::new();
+    DnsName::new("a.bc").unwrap().write(&mut out).unwrap();
+    assert_eq!(b"\x01a\x02bc\x00", out.readable());
+    let mut out: FixedBuf<4> = FixedBuf::new();
+    assert_eq!(
+        Err(DnsError::ResponseBufferFull),
+        DnsName::new("a.bc").unwrap().write(&mut out)
+    );
+}
+
+#[cfg(test)]
+#[test]
+#[allow(deprecated)]
+fn test_inner() {
+    assert_eq!("abc", DnsName::new("abc").unwrap().inner());
+}
+
+#[cfg(test)]
+#[test]
+fn test_display() {
+    assert_eq!(
+        "example.com",
+        format!("{}", DnsName::new("example.com").unwrap())
+    );
+}
+
+#[cfg(test)]
+#[test]
+fn test_labels() {
+    let name = DnsName::new("www.Example.com").unwrap();
+    assert_eq!(
+        vec![&b"www"[..], b"Example", b"com"],
+        name.labels().collect::<Vec<&[u8]>>()
+    );
+    assert_eq!(3, name.num_labels());
+    assert_eq!(0, DnsName::root().num_labels());
+    assert_eq!(None, DnsName::root().labels().next());
+    assert!(DnsName::root().is_root());
+    assert!(!name.is_root());
+    assert_eq!(".", DnsName::root().to_string());
+}
+
+#[cfg(test)]
+#[test]
+fn test_parent() {
+    let name = DnsName::new("www.example.com").unwrap();
+    assert_eq!(Some(DnsName::new("example.com").unwrap()), name.parent());
+    let com = DnsName::new("com").unwrap();
+    assert_eq!(Some(DnsName::root()), com.parent());
+    assert_eq!(None, DnsName::root().parent());
+}
+
+#[cfg(test)]
+#[test]
+fn test_is_subdomain_of() {
+    let name = DnsName::new("www.example.com").unwrap();
+    for zone in ["www.example.com", "example.com", "com"] {
+        assert!(name.is_subdomain_of(&DnsName::new(zone).unwrap()), "{zone}");
+    }
+    assert!(name.is_subdomain_of(&DnsName::root()));
+    assert!(DnsName::root().is_subdomain_of(&DnsName::root()));
+    for zone in [
+        "ww.example.com",
+        "w.example.com",
+        "xample.com",
+        "om",
+        "a.www.example.com",
+    ] {
+        assert!(
+            !name.is_subdomain_of(&DnsName::new(zone).unwrap()),
+            "{zone}"
+        );
+    }
+    assert!(!DnsName::root().is_subdomain_of(&DnsName::new("com").unwrap()));
+}
+
+#[cfg(test)]
+#[test]
+fn test_strip_suffix() {
+    let name = DnsName::new("a.b.example.com").unwrap();
+    let zone = DnsName::new("example.com").unwrap();
+    assert_eq!(Some(DnsName::new("a.b").unwrap()), name.strip_suffix(&zone));
+    assert_eq!(Some(DnsName::root()), zone.strip_suffix(&zone));
+    assert_eq!(Some(name.clone()), name.strip_suffix(&DnsName::root()));
+    assert_eq!(None, zone.strip_suffix(&name));
+    assert_eq!(None, name.strip_suffix(&DnsName::new("ample.com").unwrap()));
+}
+
+#[cfg(test)]
+#[test]
+fn test_prepend_label() {
+    let zone = DnsName::new("example.com").unwrap();
+    assert_eq!(
+        DnsName::new("www.example.com").unwrap(),
+        zone.prepend_label(b"www").unwrap()
+    );
+    assert_eq!(
+        DnsName::new("com").unwrap(),
+        DnsName::root().prepend_label(b"com").unwrap()
+    );
+    assert_eq!(Err(DnsError::InvalidLabel), zone.prepend_label(b""));
+    assert_eq!(Err(DnsError::InvalidLabel), zone.prepend_label(&[b'a'; 64]));
+    zone.prepend_label(&[b'a'; 63]).unwrap();
+    let long = DnsName::new(concat!(
         "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa.",
         "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa.",
         "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa.",
-        "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa."
+        "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"
     ))
     .unwrap();
-    DnsName::new(concat!(
+    assert_eq!(254, long.prepend_label(b"a").unwrap().0.len());
+    assert_eq!(Err(DnsError::NameTooLong), long.prepend_label(b"aa"));
+}
+
+#[cfg(test)]
+#[test]
+fn test_append() {
+    let origin = DnsName::new("example.com").unwrap();
+    assert_eq!(
+        DnsName::new("a.b.example.com").unwrap(),
+        DnsName::new("a.b").unwrap().append(&origin).unwrap()
+    );
+    assert_eq!(origin, DnsName::root().append(&origin).unwrap());
+    assert_eq!(origin, origin.append(&DnsName::root()).unwrap());
+    let long = DnsName::new(concat!(
         "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa.",
         "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa.",
         "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa.",
-        "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa.a"
-    ))
-    .unwrap_err();
-    DnsName::new(concat!(
-        "a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.",
-        "a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.",
-        "a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.",
-        "a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.",
-        "a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.",
-        "a.a.a",
+        "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"
     ))
     .unwrap();
-    DnsName::new(concat!(
-        "a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.",
-        "a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.",
-        "a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.",
-        "a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.",
-        "a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.a.",
-        "a.a.aa",
-    ))
-    .unwrap_err();
+    assert_eq!(
+        254,
+        long.append(&DnsName::new("a.b.c.d.e").unwrap())
+            .unwrap()
+            .0
+            .len()
+    );
+    assert_eq!(
+        Err(DnsError::NameTooLong),
+        long.append(&DnsName::new("a.b.c.d.ee").unwrap())
+    );
 }
 
-// TODO: Test read()
-// TODO: Test write()
+#[cfg(test)]
+#[test]
+fn test_canonical_order() {
+    // https://datatracker.ietf.org/doc/html/rfc4034#section-6.1
+    let expected: Vec<DnsName> = [
+        "example",
+        "a.example",
+        "yljkjljk.a.example",
+        "Z.a.example",
+        "zABC.a.EXAMPLE",
+        "z.example",
+        "\\001.z.example",
+        "*.z.example",
+        "\\200.z.example",
+    ]
+    .iter()
+    .map(|value| DnsName::with_syntax(value, DnsNameSyntax::Any).unwrap())
+    .collect();
+    let mut names = expected.clone();
+    names.reverse();
+    names.swap(1, 5);
+    names.sort();
+    assert_eq!(expected, names);
+    assert!(DnsName::root() < DnsName::new("a").unwrap());
+    assert!(DnsName::new("b.example").unwrap() < DnsName::new("a.b.example").unwrap());
+    assert!(DnsName::new("a.b.example").unwrap() < DnsName::new("c.example").unwrap());
+    assert!(DnsName::new("a-b.example").unwrap() < DnsName::new("a.a-b.example").unwrap());
+}
 
 #[cfg(test)]
 #[test]
-fn test_inner() {
-    assert_eq!("abc", DnsName::new("abc").unwrap().inner());
+fn test_canonical_order_case() {
+    let mut buf: FixedBuf<16> = FixedBuf::new();
+    buf.write_bytes(b"\x01B\x01a\x00").unwrap();
+    let upper = DnsName::read(&mut buf).unwrap();
+    assert_eq!("B.a", upper.to_string());
+    let lower = DnsName::new("b.a").unwrap();
+    assert_eq!(upper, lower);
+    assert_eq!(Ordering::Equal, upper.cmp(&lower));
+    let mut set = std::collections::HashSet::new();
+    set.insert(upper.clone());
+    assert!(set.contains(&lower));
+    assert!(upper.is_subdomain_of(&DnsName::new("A").unwrap()));
+    assert_eq!(
+        Some(DnsName::new("b").unwrap()),
+        upper.strip_suffix(&DnsName::new("A").unwrap())
+    );
+    assert!(DnsName::new("a.a").unwrap() < upper);
+    assert!(upper < DnsName::new("c.a").unwrap());
 }
 
 #[cfg(test)]
 #[test]
-fn test_display() {
+fn test_from_ip() {
+    let name = |value: &str| DnsName::with_syntax(value, DnsNameSyntax::Any).unwrap();
     assert_eq!(
-        "example.com",
-        format!("{}", DnsName::new("example.com").unwrap())
+        name("4.3.2.1.in-addr.arpa"),
+        DnsName::from_ip("1.2.3.4".parse().unwrap())
+    );
+    assert_eq!(
+        name("b.a.9.8.7.6.5.0.4.0.0.0.3.0.0.0.2.0.0.0.1.0.0.0.0.0.0.0.1.2.3.4.ip6.arpa"),
+        DnsName::from_ip("4321:0:1:2:3:4:567:89ab".parse().unwrap())
+    );
+    assert_eq!(
+        name("100.99.10.9.in-addr.arpa")
+            .as_bytes()
+            .unwrap()
+            .readable(),
+        DnsName::from_ip("9.10.99.100".parse().unwrap())
+            .as_bytes()
+            .unwrap()
+            .readable()
+    );
+    assert_eq!(
+        name("f.f.f.f.f.f.f.f.f.f.f.f.f.f.f.f.f.f.f.f.f.f.f.f.f.f.f.f.f.f.f.f.ip6.arpa")
+            .as_bytes()
+            .unwrap()
+            .readable(),
+        DnsName::from_ip("ffff:ffff:ffff:ffff:ffff:ffff:ffff:ffff".parse().unwrap())
+            .as_bytes()
+            .unwrap()
+            .readable()
+    );
+    let from_prefix =
+        |addr: &str, prefix_len: u8| DnsName::from_ip_prefix(addr.parse().unwrap(), prefix_len);
+    assert_eq!(
+        Ok(name("192/26.255.255.255.in-addr.arpa")),
+        from_prefix("255.255.255.255", 26)
     );
+    assert_eq!(
+        Ok(name("2.0.192.in-addr.arpa")),
+        from_prefix("192.0.2.1", 24)
+    );
+    assert_eq!(Ok(name("0.192.in-addr.arpa")), from_prefix("192.0.2.1", 16));
+    assert_eq!(Ok(name("192.in-addr.arpa")), from_prefix("192.0.2.1", 8));
+    assert_eq!(Ok(name("in-addr.arpa")), from_prefix("192.0.2.1", 0));
+    assert_eq!(
+        Ok(name("1.2.0.192.in-addr.arpa")),
+        from_prefix("192.0.2.1", 32)
+    );
+    assert_eq!(
+        Ok(name("64/26.2.0.192.in-addr.arpa")),
+        from_prefix("192.0.2.100", 26)
+    );
+    assert_eq!(
+        Ok(name("128/25.2.0.192.in-addr.arpa")),
+        from_prefix("192.0.2.200", 25)
+    );
+    assert_eq!(
+        Ok(name("2/31.2.0.192.in-addr.arpa")),
+        from_prefix("192.0.2.3", 31)
+    );
+    from_prefix("192.0.2.1", 20).unwrap_err();
+    from_prefix("192.0.2.1", 33).unwrap_err();
+    assert_eq!(
+        Ok(name("8.b.d.0.1.0.0.2.ip6.arpa")),
+        from_prefix("2001:db8::1", 32)
+    );
+    assert_eq!(Ok(name("ip6.arpa")), from_prefix("2001:db8::1", 0));
+    from_prefix("2001:db8::1", 33).unwrap_err();
+    from_prefix("2001:db8::1", 132).unwrap_err();
+}
+
+#[cfg(test)]
+#[test]
+fn test_to_ip() {
+    let to_ip = |value: &str| {
+        DnsName::with_syntax(value, DnsNameSyntax::Any)
+            .unwrap()
+            .to_ip()
+    };
+    assert_eq!(
+        Some("1.2.3.4".parse().unwrap()),
+        to_ip("4.3.2.1.in-addr.arpa")
+    );
+    assert_eq!(
+        Some("1.2.3.4".parse().unwrap()),
+        to_ip("4.3.2.1.IN-ADDR.ARPA.")
+    );
+    assert_eq!(
+        Some("192.0.2.65".parse().unwrap()),
+        to_ip("65.64/26.2.0.192.in-addr.arpa")
+    );
+    assert_eq!(None, to_ip("1.64/26.2.0.192.in-addr.arpa"));
+    assert_eq!(None, to_ip("65.65/26.2.0.192.in-addr.arpa"));
+    assert_eq!(None, to_ip("3.2.1.in-addr.arpa"));
+    assert_eq!(None, to_ip("5.4.3.2.1.in-addr.arpa"));
+    assert_eq!(None, to_ip("256.3.2.1.in-addr.arpa"));
+    assert_eq!(None, to_ip("04.3.2.1.in-addr.arpa"));
+    assert_eq!(None, to_ip("a.3.2.1.in-addr.arpa"));
+    assert_eq!(None, to_ip("4.3.2.1.in-addr.example"));
+    assert_eq!(None, to_ip("arpa"));
+    assert_eq!(
+        Some("4321:0:1:2:3:4:567:89ab".parse().unwrap()),
+        to_ip("B.A.9.8.7.6.5.0.4.0.0.0.3.0.0.0.2.0.0.0.1.0.0.0.0.0.0.0.1.2.3.4.ip6.arpa")
+    );
+    assert_eq!(None, to_ip("8.b.d.0.1.0.0.2.ip6.arpa"));
+    assert_eq!(
+        None,
+        to_ip("g.a.9.8.7.6.5.0.4.0.0.0.3.0.0.0.2.0.0.0.1.0.0.0.0.0.0.0.1.2.3.4.ip6.arpa")
+    );
+    for addr in [
+        "0.0.0.0",
+        "10.20.30.40",
+        "255.255.255.255",
+        "::",
+        "2001:db8::1",
+    ] {
+        let addr: IpAddr = addr.parse().unwrap();
+        assert_eq!(Some(addr), DnsName::from_ip(addr).to_ip());
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_to_ip_prefix() {
+    let to_ip_prefix = |value: &str| {
+        DnsName::with_syntax(value, DnsNameSyntax::Any)
+            .unwrap()
+            .to_ip_prefix()
+    };
+    assert_eq!(
+        Some(("192.0.2.0".parse().unwrap(), 24)),
+        to_ip_prefix("2.0.192.in-addr.arpa")
+    );
+    assert_eq!(
+        Some(("192.0.0.0".parse().unwrap(), 8)),
+        to_ip_prefix("192.in-addr.arpa")
+    );
+    assert_eq!(
+        Some(("0.0.0.0".parse().unwrap(), 0)),
+        to_ip_prefix("in-addr.arpa")
+    );
+    assert_eq!(
+        Some(("192.0.2.64".parse().unwrap(), 26)),
+        to_ip_prefix("64/26.2.0.192.in-addr.arpa")
+    );
+    assert_eq!(
+        Some(("192.0.2.1".parse().unwrap(), 32)),
+        to_ip_prefix("1.2.0.192.in-addr.arpa")
+    );
+    assert_eq!(None, to_ip_prefix("65/26.2.0.192.in-addr.arpa"));
+    assert_eq!(None, to_ip_prefix("1.64/26.2.0.192.in-addr.arpa"));
+    assert_eq!(
+        Some(("2001:db8::".parse().unwrap(), 32)),
+        to_ip_prefix("8.b.d.0.1.0.0.2.ip6.arpa")
+    );
+    assert_eq!(None, to_ip_prefix("www.example.com"));
+    for (addr, prefix_len) in [("192.0.2.128", 25), ("10.0.0.0", 8), ("2001:db8::", 48)] {
+        let addr: IpAddr = addr.parse().unwrap();
+        assert_eq!(
+            Some((addr, prefix_len)),
+            DnsName::from_ip_prefix(addr, prefix_len)
+                .unwrap()
+                .to_ip_prefix()
+        );
+    }
 }
 
 // TODO: Test TryFrom
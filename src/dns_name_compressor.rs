@@ -0,0 +1,97 @@
+use crate::{DnsError, DnsRecord};
+use fixed_buffer::FixedBuf;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+/// Remembers where names appear in a message, so later names can point to them.
+///
+/// > In order to reduce the size of messages, the domain system utilizes a compression scheme
+/// > which eliminates the repetition of domain names in a message.  In this scheme, an entire
+/// > domain name or a list of labels at the end of a domain name is replaced with a pointer to
+/// > a prior occurance of the same name.
+/// >
+/// > ...
+/// >
+/// > Programs are free to avoid using pointers in messages they generate, although this will
+/// > reduce datagram capacity, and may cause truncation.  However all programs are required to
+/// > understand arriving messages that contain pointers.
+///
+/// <https://datatracker.ietf.org/doc/html/rfc1035#section-4.1.4>
+#[derive(Clone, Debug)]
+pub(crate) struct DnsNameCompressor {
+    enabled: bool,
+    /// Position in the current output buffer that has message offset `message_offset`.
+    buf_start: usize,
+    message_offset: usize,
+    /// Lowercase name suffixes, as length-prefixed labels,
+    /// and their offsets from the start of the message.
+    suffixes: HashMap<Vec<u8>, u16>,
+}
+impl DnsNameCompressor {
+    fn write_position<const N: usize>(out: &mut FixedBuf<N>) -> usize {
+        N - out.writable().len()
+    }
+
+    /// Makes a compressor for a message that starts at the current write position of `out`.
+    pub fn new<const N: usize>(out: &mut FixedBuf<N>) -> Self {
+        Self {
+            enabled: true,
+            buf_start: Self::write_position(out),
+            message_offset: 0,
+            suffixes: HashMap::new(),
+        }
+    }
+
+    /// Makes a compressor that never emits pointers.
+    /// Use this to write names outside of a message.
+    pub fn disabled() -> Self {
+        Self {
+            enabled: false,
+            buf_start: 0,
+            message_offset: 0,
+            suffixes: HashMap::new(),
+        }
+    }
+
+    /// Returns the pointer offset of a previously-written `suffix`.
+    pub fn get(&self, suffix: &[u8]) -> Option<u16> {
+        self.suffixes.get(&suffix.to_ascii_lowercase()).copied()
+    }
+
+    /// Remembers that `suffix` is about to be written at the current position of `out`.
+    /// Does nothing when the position is too far into the message for a pointer to reach.
+    pub fn insert<const N: usize>(&mut self, suffix: &[u8], out: &mut FixedBuf<N>) {
+        let suffix = suffix.to_ascii_lowercase();
+        if !self.enabled || self.suffixes.contains_key(&suffix) {
+            return;
+        }
+        let offset = Self::write_position(out) - self.buf_start + self.message_offset;
+        if let Ok(offset) = u16::try_from(offset) {
+            if offset < 0x4000 {
+                self.suffixes.insert(suffix, offset);
+            }
+        }
+    }
+
+    /// Calls `f` to write RDATA into a temporary buffer and then writes it to `out`, prefixed by
+    /// its length.  Names that `f` writes with this compressor can point to earlier names and
+    /// later names can point to them.
+    ///
+    /// # Errors
+    /// Returns an error when `f` fails or `out` fills up.
+    pub fn write_rdata<const N: usize>(
+        &mut self,
+        out: &mut FixedBuf<N>,
+        f: impl FnOnce(&mut FixedBuf<N>, &mut Self) -> Result<(), DnsError>,
+    ) -> Result<(), DnsError> {
+        let rdata_offset = Self::write_position(out) - self.buf_start + self.message_offset + 2;
+        let saved = (self.buf_start, self.message_offset);
+        self.buf_start = 0;
+        self.message_offset = rdata_offset;
+        let mut rdata: FixedBuf<N> = FixedBuf::new();
+        let result = f(&mut rdata, self);
+        (self.buf_start, self.message_offset) = saved;
+        result?;
+        DnsRecord::write_rdata(rdata.readable(), out)
+    }
+}
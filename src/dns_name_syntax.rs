@@ -0,0 +1,137 @@
+/// Rules for the labels of a [`DnsName`](crate::DnsName).
+///
+/// > The labels must follow the rules for ARPANET host names.  They must start with a letter, end
+/// > with a letter or digit, and have as interior characters only letters, digits, and hyphen.
+///
+/// <https://datatracker.ietf.org/doc/html/rfc1035#section-2.3.1>
+///
+/// > The syntax of a legal Internet host name was specified in RFC-952 [DNS:4].  One aspect of
+/// > host name syntax is hereby changed: the restriction on the first character is relaxed to
+/// > allow either a letter or a digit.
+///
+/// <https://datatracker.ietf.org/doc/html/rfc1123#section-2.1>
+///
+/// > The DNS itself places only one restriction on the particular labels that can be used to
+/// > identify resource records.  That one restriction relates to the length of the label and the
+/// > full name.
+///
+/// <https://datatracker.ietf.org/doc/html/rfc2181#section-11>
+///
+/// > ... the DNS protocol uses underscored names as a way of scoping resource records, such as
+/// > `_tcp` and `_udp` for SRV records and `_dmarc` for TXT records.
+///
+/// <https://datatracker.ietf.org/doc/html/rfc8552#section-1.1>
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum DnsNameSyntax {
+    /// RFC 1035 preferred name syntax: labels start with a letter, end with a letter or digit,
+    /// and contain only letters, digits, and hyphens.
+    Rfc1035,
+    /// RFC 1123 host names: like `Rfc1035`, but labels may also start with a digit,
+    /// like `3com.com` and `4.3.2.1.in-addr.arpa`.
+    /// Use this for the names of A and AAAA records.
+    Hostname,
+    /// Host names plus labels that start with an underscore,
+    /// like `_acme-challenge`, `_sip._tcp`, and `_dmarc`.
+    Service,
+    /// RFC 2181 labels: any octets.
+    /// [`DnsName::read`](crate::DnsName::read) uses this.
+    Any,
+}
+impl DnsNameSyntax {
+    fn is_letter(b: u8) -> bool {
+        b.is_ascii_lowercase() || b.is_ascii_uppercase()
+    }
+
+    fn is_letter_digit(b: u8) -> bool {
+        Self::is_letter(b) || b.is_ascii_digit()
+    }
+
+    fn is_letter_digit_hyphen(b: u8) -> bool {
+        Self::is_letter_digit(b) || b == b'-'
+    }
+
+    fn is_valid_ldh_label(label: &[u8], is_valid_first: fn(u8) -> bool) -> bool {
+        match (label.first(), label.last()) {
+            (Some(first), Some(last)) => {
+                is_valid_first(*first)
+                    && label.iter().copied().all(Self::is_letter_digit_hyphen)
+                    && Self::is_letter_digit(*last)
+            }
+            _ => false,
+        }
+    }
+
+    fn is_valid_hostname_label(label: &[u8]) -> bool {
+        Self::is_valid_ldh_label(label, Self::is_letter_digit)
+    }
+
+    #[must_use]
+    pub fn is_valid_label(self, label: &[u8]) -> bool {
+        if label.is_empty() || label.len() > 63 {
+            return false;
+        }
+        match self {
+            DnsNameSyntax::Rfc1035 => Self::is_valid_ldh_label(label, Self::is_letter),
+            DnsNameSyntax::Hostname => Self::is_valid_hostname_label(label),
+            DnsNameSyntax::Service => match label.strip_prefix(b"_") {
+                Some(rest) => Self::is_valid_hostname_label(rest),
+                None => Self::is_valid_hostname_label(label),
+            },
+            DnsNameSyntax::Any => true,
+        }
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_is_valid_label() {
+    use DnsNameSyntax::{Any, Hostname, Rfc1035, Service};
+    for label in ["a", "a1", "a-b", "example"] {
+        for syntax in [Rfc1035, Hostname, Service, Any] {
+            assert!(
+                syntax.is_valid_label(label.as_bytes()),
+                "{syntax:?} {label:?}"
+            );
+        }
+    }
+    for label in ["1", "3com", "1-a", "0"] {
+        assert!(!Rfc1035.is_valid_label(label.as_bytes()), "{label:?}");
+        for syntax in [Hostname, Service, Any] {
+            assert!(
+                syntax.is_valid_label(label.as_bytes()),
+                "{syntax:?} {label:?}"
+            );
+        }
+    }
+    for label in ["_acme-challenge", "_tcp", "_dmarc", "_sip", "_25"] {
+        assert!(!Rfc1035.is_valid_label(label.as_bytes()), "{label:?}");
+        assert!(!Hostname.is_valid_label(label.as_bytes()), "{label:?}");
+        assert!(Service.is_valid_label(label.as_bytes()), "{label:?}");
+        assert!(Any.is_valid_label(label.as_bytes()), "{label:?}");
+    }
+    for label in [
+        &b"_"[..],
+        b"__a",
+        b"a_b",
+        b"a_",
+        b"_-a",
+        b"_a-",
+        b"-a",
+        b"a-",
+        b"a b",
+        b"a!",
+        b"a.b",
+        b"\x00",
+        b"\xFF",
+    ] {
+        for syntax in [Rfc1035, Hostname, Service] {
+            assert!(!syntax.is_valid_label(label), "{syntax:?} {label:?}");
+        }
+        assert!(Any.is_valid_label(label), "{label:?}");
+    }
+    for label in [&b""[..], &[b'a'; 64]] {
+        for syntax in [Rfc1035, Hostname, Service, Any] {
+            assert!(!syntax.is_valid_label(label), "{syntax:?} {label:?}");
+        }
+    }
+}
@@ -0,0 +1,308 @@
+use core::fmt::Formatter;
+use core::str::FromStr;
+
+/// Reads the rest of an escape sequence, after the `\`.
+///
+/// > `\X` where X is any character other than a digit (0-9), is used to quote that character
+/// > so that its special meaning does not apply.
+/// >
+/// > `\DDD` where each D is a digit is the octet corresponding to the decimal number described
+/// > by DDD.
+///
+/// <https://datatracker.ietf.org/doc/html/rfc1035#section-5.1>
+fn read_escape(bytes: &mut impl Iterator<Item = u8>) -> Option<u8> {
+    let escaped = bytes.next()?;
+    if !escaped.is_ascii_digit() {
+        return Some(escaped);
+    }
+    let mut n = u16::from(escaped - b'0');
+    for _ in 0..2 {
+        let digit = bytes.next().filter(u8::is_ascii_digit)?;
+        n = n * 10 + u16::from(digit - b'0');
+    }
+    u8::try_from(n).ok()
+}
+
+/// Splits presentation format RDATA into fields and removes quotes and escapes.
+///
+/// > `<character-string>` is expressed in one or two ways: as a contiguous set of characters
+/// > without interior spaces, or as a string beginning with a " and ending with a ".  Inside a "
+/// > delimited string any character can occur, except for a " itself, which must be quoted
+/// > using \ (back slash).
+///
+/// <https://datatracker.ietf.org/doc/html/rfc1035#section-5.1>
+///
+/// # Errors
+/// Returns an error when `value` contains an unterminated quoted string or a bad escape.
+pub(crate) fn split_fields(value: &str) -> Result<Vec<Vec<u8>>, String> {
+    let err = || format!("not valid presentation format: {value:?}");
+    let mut fields: Vec<Vec<u8>> = Vec::new();
+    let mut bytes = value.bytes().peekable();
+    loop {
+        while bytes.next_if(u8::is_ascii_whitespace).is_some() {}
+        let Some(first) = bytes.next() else {
+            return Ok(fields);
+        };
+        let mut field: Vec<u8> = Vec::new();
+        if first == b'"' {
+            loop {
+                match bytes.next().ok_or_else(err)? {
+                    b'"' => break,
+                    b'\\' => field.push(read_escape(&mut bytes).ok_or_else(err)?),
+                    b => field.push(b),
+                }
+            }
+            if bytes.peek().is_some_and(|b| !b.is_ascii_whitespace()) {
+                return Err(err());
+            }
+        } else {
+            let mut b = first;
+            loop {
+                match b {
+                    b'\\' => field.push(read_escape(&mut bytes).ok_or_else(err)?),
+                    b'"' => return Err(err()),
+                    b => field.push(b),
+                }
+                match bytes.next_if(|b| !b.is_ascii_whitespace()) {
+                    Some(next) => b = next,
+                    None => break,
+                }
+            }
+        }
+        fields.push(field);
+    }
+}
+
+/// Parses a decimal number field.
+///
+/// # Errors
+/// Returns an error when `field` is not a decimal number that fits in `T`.
+pub(crate) fn parse_number<T: FromStr>(field: &[u8], field_name: &str) -> Result<T, String> {
+    std::str::from_utf8(field)
+        .ok()
+        .filter(|s| s.bytes().all(|b| b.is_ascii_digit()))
+        .and_then(|s| s.parse().ok())
+        .ok_or_else(|| {
+            format!(
+                "{field_name} is not a valid number: {:?}",
+                String::from_utf8_lossy(field)
+            )
+        })
+}
+
+/// Writes `bytes` as a quoted presentation format `<character-string>`.
+pub(crate) fn write_character_string(
+    f: &mut Formatter<'_>,
+    bytes: &[u8],
+) -> Result<(), core::fmt::Error> {
+    write!(f, "\"")?;
+    for b in bytes {
+        match b {
+            b'"' | b'\\' => write!(f, "\\{}", char::from(*b))?,
+            0x20..=0x7E => write!(f, "{}", char::from(*b))?,
+            _ => write!(f, "\\{b:03}")?,
+        }
+    }
+    write!(f, "\"")
+}
+
+/// Encodes `bytes` as uppercase hexadecimal digits.
+pub(crate) fn hex_encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02X}")).collect()
+}
+
+/// Parses a field of hexadecimal digits.
+///
+/// # Errors
+/// Returns an error when `field` is not an even number of hexadecimal digits.
+pub(crate) fn parse_hex(field: &[u8], field_name: &str) -> Result<Vec<u8>, String> {
+    let err = || {
+        format!(
+            "{field_name} is not valid hexadecimal: {:?}",
+            String::from_utf8_lossy(field)
+        )
+    };
+    if !field.len().is_multiple_of(2) {
+        return Err(err());
+    }
+    field
+        .chunks(2)
+        .map(|pair| {
+            std::str::from_utf8(pair)
+                .ok()
+                .filter(|s| s.bytes().all(|b| b.is_ascii_hexdigit()))
+                .and_then(|s| u8::from_str_radix(s, 16).ok())
+                .ok_or_else(err)
+        })
+        .collect()
+}
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Encodes `bytes` with the padded base 64 encoding.
+///
+/// <https://datatracker.ietf.org/doc/html/rfc4648#section-4>
+pub(crate) fn base64_encode(bytes: &[u8]) -> String {
+    let mut result = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0_u32, |n, (i, b)| n | (u32::from(*b) << (16 - 8 * i)));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                let index = (n >> (18 - 6 * i)) & 0x3F;
+                result.push(char::from(BASE64_ALPHABET[index as usize]));
+            } else {
+                result.push('=');
+            }
+        }
+    }
+    result
+}
+
+/// Decodes padded base 64 `encoded`.
+/// Returns `None` when `encoded` is not valid base 64.
+///
+/// <https://datatracker.ietf.org/doc/html/rfc4648#section-4>
+#[cfg(any(test, all(feature = "sha1", feature = "sha2")))]
+pub(crate) fn base64_decode(encoded: &[u8]) -> Option<Vec<u8>> {
+    if !encoded.len().is_multiple_of(4) {
+        return None;
+    }
+    let mut result = Vec::with_capacity(encoded.len() / 4 * 3);
+    let last_chunk = encoded.len() / 4;
+    for (chunk_index, chunk) in encoded.chunks(4).enumerate() {
+        let padding = chunk.iter().rev().take_while(|b| **b == b'=').count();
+        if padding > 2 || (padding > 0 && chunk_index + 1 != last_chunk) {
+            return None;
+        }
+        let mut n = 0_u32;
+        for b in &chunk[..4 - padding] {
+            let index = BASE64_ALPHABET.iter().position(|a| a == b)?;
+            n = (n << 6) | u32::try_from(index).ok()?;
+        }
+        n <<= 6 * padding;
+        let bytes = n.to_be_bytes();
+        result.extend_from_slice(&bytes[1..4 - padding]);
+    }
+    Some(result)
+}
+
+#[cfg(test)]
+#[test]
+fn test_split_fields() {
+    let split = |value: &str| {
+        split_fields(value).map(|fields| {
+            fields
+                .into_iter()
+                .map(|field| String::from_utf8(field).unwrap())
+                .collect::<Vec<String>>()
+        })
+    };
+    assert_eq!(Ok(Vec::<String>::new()), split(""));
+    assert_eq!(Ok(Vec::<String>::new()), split(" \t "));
+    assert_eq!(
+        Ok(vec![
+            "0".to_string(),
+            "issue".to_string(),
+            "ca.example".to_string()
+        ]),
+        split("0 issue \"ca.example\"")
+    );
+    assert_eq!(
+        Ok(vec!["a b".to_string(), "".to_string(), "c".to_string()]),
+        split("  \"a b\"  \"\" c  ")
+    );
+    assert_eq!(
+        Ok(vec![
+            "a\"b\\c".to_string(),
+            "A".to_string(),
+            "x y".to_string()
+        ]),
+        split("\"a\\\"b\\\\c\" \\065 x\\ y")
+    );
+    for value in [
+        "\"abc",
+        "\"abc\"def",
+        "ab\"c",
+        "\\",
+        "\"\\",
+        "\\25",
+        "\\256",
+        "\\2a5",
+    ] {
+        split(value).unwrap_err();
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_parse_number() {
+    assert_eq!(Ok(0_u8), parse_number(b"0", "n"));
+    assert_eq!(Ok(255_u8), parse_number(b"255", "n"));
+    assert_eq!(Ok(65535_u16), parse_number(b"65535", "n"));
+    parse_number::<u8>(b"256", "n").unwrap_err();
+    parse_number::<u8>(b"", "n").unwrap_err();
+    parse_number::<u8>(b"+1", "n").unwrap_err();
+    parse_number::<u8>(b"-1", "n").unwrap_err();
+    parse_number::<u8>(b"1a", "n").unwrap_err();
+}
+
+#[cfg(test)]
+#[test]
+fn test_hex() {
+    assert_eq!("", hex_encode(b""));
+    assert_eq!("00FF0A", hex_encode(b"\x00\xFF\x0A"));
+    assert_eq!(Ok(vec![]), parse_hex(b"", "x"));
+    assert_eq!(
+        Ok(vec![0x00, 0xFF, 0x0A, 0xBC]),
+        parse_hex(b"00ff0ABc", "x")
+    );
+    parse_hex(b"0", "x").unwrap_err();
+    parse_hex(b"0g", "x").unwrap_err();
+    parse_hex(b"+1", "x").unwrap_err();
+    parse_hex(b"\xC3\xA9", "x").unwrap_err();
+}
+
+#[cfg(test)]
+#[test]
+fn test_base64_encode() {
+    // https://datatracker.ietf.org/doc/html/rfc4648#section-10
+    for (bytes, expected) in [
+        (&b""[..], ""),
+        (b"f", "Zg=="),
+        (b"fo", "Zm8="),
+        (b"foo", "Zm9v"),
+        (b"foob", "Zm9vYg=="),
+        (b"fooba", "Zm9vYmE="),
+        (b"foobar", "Zm9vYmFy"),
+        (b"\xFF\xFE\xFD", "//79"),
+    ] {
+        assert_eq!(expected, base64_encode(bytes));
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_base64_decode() {
+    // https://datatracker.ietf.org/doc/html/rfc4648#section-10
+    for (expected, encoded) in [
+        (&b""[..], ""),
+        (b"f", "Zg=="),
+        (b"fo", "Zm8="),
+        (b"foo", "Zm9v"),
+        (b"foob", "Zm9vYg=="),
+        (b"fooba", "Zm9vYmE="),
+        (b"foobar", "Zm9vYmFy"),
+        (b"\xFF\xFE\xFD", "//79"),
+    ] {
+        assert_eq!(Some(expected.to_vec()), base64_decode(encoded.as_bytes()));
+    }
+    for encoded in [
+        "Zg", "Zg=", "Z===", "Zg==Zm9v", "Zm9v\n", "Zm-v", "Zm9v====", "====",
+    ] {
+        assert_eq!(None, base64_decode(encoded.as_bytes()), "{encoded:?}");
+    }
+}
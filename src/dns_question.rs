@@ -1,4 +1,5 @@
 use crate::dns_class::DnsClass;
+use crate::dns_name_compressor::DnsNameCompressor;
 use crate::{DnsError, DnsName, DnsType};
 use fixed_buffer::FixedBuf;
 
@@ -31,17 +32,26 @@ impl DnsQuestion {
     pub fn read<const N: usize>(buf: &mut FixedBuf<N>) -> Result<Self, DnsError> {
         let name = DnsName::read(buf)?;
         let typ = DnsType::read(buf)?;
+        // Keep questions of every class, like CH `version.bind`, so messages round-trip.
+        // Lookups answer only from records in the question's class.
         let class = DnsClass::read(buf)?;
-        if class != DnsClass::Internet && class != DnsClass::Any {
-            return Err(DnsError::InvalidClass);
-        }
         Ok(DnsQuestion { name, typ, class })
     }
 
     /// # Errors
     /// Returns an error when `buf` fills up.
     pub fn write<const N: usize>(&self, out: &mut FixedBuf<N>) -> Result<(), DnsError> {
-        self.name.write(out)?;
+        self.write_compressed(out, &mut DnsNameCompressor::disabled())
+    }
+
+    /// # Errors
+    /// Returns an error when `buf` fills up.
+    pub(crate) fn write_compressed<const N: usize>(
+        &self,
+        out: &mut FixedBuf<N>,
+        compressor: &mut DnsNameCompressor,
+    ) -> Result<(), DnsError> {
+        self.name.write_compressed(out, compressor)?;
         self.typ.write(out)?;
         self.class.write(out)?;
         Ok(())
@@ -1,6 +1,12 @@
+use crate::dns_name_compressor::DnsNameCompressor;
+#[cfg(all(feature = "sha1", feature = "sha2"))]
+use crate::dns_presentation::base64_decode;
+use crate::dns_presentation::{
+    hex_encode, parse_hex, parse_number, split_fields, write_character_string,
+};
 use crate::{
-    read_exact, read_u16_be, read_u32_be, write_bytes, write_u16_be, write_u32_be, DnsClass,
-    DnsError, DnsName, DnsType,
+    read_exact, read_u16_be, read_u32_be, read_u8, write_bytes, write_u16_be, write_u32_be,
+    DnsClass, DnsError, DnsName, DnsNameSyntax, DnsSvcParam, DnsType,
 };
 use core::fmt::{Debug, Formatter};
 use fixed_buffer::FixedBuf;
@@ -53,20 +59,309 @@ use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
 /// <https://datatracker.ietf.org/doc/html/rfc1035#section-4.1.3>
 #[derive(Clone, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub enum DnsRecord {
-    A(DnsName, std::net::Ipv4Addr),
-    AAAA(DnsName, std::net::Ipv6Addr),
-    CNAME(DnsName, DnsName),
-    Unknown(DnsName, DnsType),
+    A {
+        name: DnsName,
+        class: DnsClass,
+        ttl: u32,
+        addr: Ipv4Addr,
+    },
+    AAAA {
+        name: DnsName,
+        class: DnsClass,
+        ttl: u32,
+        addr: Ipv6Addr,
+    },
+    CNAME {
+        name: DnsName,
+        class: DnsClass,
+        ttl: u32,
+        target: DnsName,
+    },
+    /// > `PREFERENCE` A 16 bit integer which specifies the preference given to this RR among
+    /// > others at the same owner.  Lower values are preferred.
+    /// >
+    /// > `EXCHANGE` A `<domain-name>` which specifies a host willing to act as a mail exchange
+    /// > for the owner name.
+    ///
+    /// <https://datatracker.ietf.org/doc/html/rfc1035#section-3.3.9>
+    MX {
+        name: DnsName,
+        class: DnsClass,
+        ttl: u32,
+        preference: u16,
+        exchange: DnsName,
+    },
+    /// > `NSDNAME` A `<domain-name>` which specifies a host which should be authoritative for the
+    /// > specified class and domain.
+    ///
+    /// <https://datatracker.ietf.org/doc/html/rfc1035#section-3.3.11>
+    NS {
+        name: DnsName,
+        class: DnsClass,
+        ttl: u32,
+        host: DnsName,
+    },
+    /// > `MNAME` The `<domain-name>` of the name server that was the original or primary source
+    /// > of data for this zone.
+    /// >
+    /// > `RNAME` A `<domain-name>` which specifies the mailbox of the person responsible for
+    /// > this zone.
+    /// >
+    /// > `SERIAL` The unsigned 32 bit version number of the original copy of the zone.
+    /// >
+    /// > `REFRESH` A 32 bit time interval before the zone should be refreshed.
+    /// >
+    /// > `RETRY` A 32 bit time interval that should elapse before a failed refresh should be
+    /// > retried.
+    /// >
+    /// > `EXPIRE` A 32 bit time value that specifies the upper limit on the time interval that
+    /// > can elapse before the zone is no longer authoritative.
+    /// >
+    /// > `MINIMUM` The unsigned 32 bit minimum TTL field that should be exported with any RR
+    /// > from this zone.
+    ///
+    /// <https://datatracker.ietf.org/doc/html/rfc1035#section-3.3.13>
+    ///
+    /// RFC 2308 changed the meaning of `MINIMUM` to the TTL of negative responses.
+    SOA {
+        name: DnsName,
+        class: DnsClass,
+        ttl: u32,
+        mname: DnsName,
+        rname: DnsName,
+        serial: u32,
+        refresh: u32,
+        retry: u32,
+        expire: u32,
+        minimum: u32,
+    },
+    /// > `TXT-DATA` One or more `<character-string>`s.
+    ///
+    /// <https://datatracker.ietf.org/doc/html/rfc1035#section-3.3.14>
+    ///
+    /// > `<character-string>` is a single length octet followed by that number of characters.
+    ///
+    /// <https://datatracker.ietf.org/doc/html/rfc1035#section-3.3>
+    ///
+    /// Each string is at most 255 bytes long.
+    TXT {
+        name: DnsName,
+        class: DnsClass,
+        ttl: u32,
+        strings: Vec<Vec<u8>>,
+    },
+    /// > `PTRDNAME` A `<domain-name>` which points to some location in the domain name space.
+    ///
+    /// <https://datatracker.ietf.org/doc/html/rfc1035#section-3.3.12>
+    PTR {
+        name: DnsName,
+        class: DnsClass,
+        ttl: u32,
+        target: DnsName,
+    },
+    /// > Priority: The priority of this target host.  A client MUST attempt to contact the target
+    /// > host with the lowest-numbered priority it can reach; target hosts with the same priority
+    /// > SHOULD be tried in an order defined by the weight field.
+    /// >
+    /// > Weight: A server selection mechanism.  The weight field specifies a relative weight for
+    /// > entries with the same priority.
+    /// >
+    /// > Port: The port on this target host of this service.
+    /// >
+    /// > Target: The domain name of the target host.  ...  A Target of "." means that the
+    /// > service is decidedly not available at this domain.
+    ///
+    /// <https://datatracker.ietf.org/doc/html/rfc2782>
+    SRV {
+        name: DnsName,
+        class: DnsClass,
+        ttl: u32,
+        priority: u16,
+        weight: u16,
+        port: u16,
+        target: DnsName,
+    },
+    /// > Flags:  One octet containing the following field:
+    /// >
+    /// > Bit 0, Issuer Critical Flag:  If the value is set to "1", the Property is critical.
+    /// > A CA MUST NOT issue certificates for any FQDN if the Relevant RRset for that FQDN
+    /// > contains a CAA critical Property for an unknown or unsupported Property Tag.
+    /// >
+    /// > ...
+    /// >
+    /// > Tag:  The Property identifier -- a sequence of ASCII characters.
+    /// >
+    /// > Tags MAY contain ASCII characters "a" through "z", "A" through "Z", and the numbers 0
+    /// > through 9.  Tags MUST NOT contain any other characters.  Matching of tags is case
+    /// > insensitive.
+    /// >
+    /// > ...
+    /// >
+    /// > Value:  A sequence of octets representing the Property Value.
+    ///
+    /// <https://datatracker.ietf.org/doc/html/rfc8659#section-4.1>
+    CAA {
+        name: DnsName,
+        class: DnsClass,
+        ttl: u32,
+        flags: u8,
+        tag: String,
+        value: Vec<u8>,
+    },
+    /// > SvcPriority:  The priority of this record (relative to others, with lower values
+    /// > preferred).  A value of 0 indicates AliasMode.
+    /// >
+    /// > TargetName:  The domain name of either the alias target (for AliasMode) or the
+    /// > alternative endpoint (for ServiceMode).
+    /// >
+    /// > SvcParams (optional):  A list of key=value pairs describing the alternative endpoint at
+    /// > TargetName (only used in ServiceMode and otherwise ignored).
+    ///
+    /// <https://datatracker.ietf.org/doc/html/rfc9460#section-2.1>
+    SVCB {
+        name: DnsName,
+        class: DnsClass,
+        ttl: u32,
+        priority: u16,
+        target: DnsName,
+        params: Vec<DnsSvcParam>,
+    },
+    /// Like `SVCB`, for HTTPS origins.
+    ///
+    /// <https://datatracker.ietf.org/doc/html/rfc9460#section-9>
+    HTTPS {
+        name: DnsName,
+        class: DnsClass,
+        ttl: u32,
+        priority: u16,
+        target: DnsName,
+        params: Vec<DnsSvcParam>,
+    },
+    /// > The TLSA RDATA format is composed of:
+    /// >
+    /// > Certificate Usage:  A one-octet value ... specifies the provided association that will
+    /// > be used to match the certificate presented in the TLS handshake.
+    /// >
+    /// > Selector:  A one-octet value ... specifies which part of the TLS certificate presented
+    /// > by the server will be matched against the association data.
+    /// >
+    /// > Matching Type:  A one-octet value ... specifies how the certificate association is
+    /// > presented.
+    /// >
+    /// > Certificate Association Data:  This field specifies the "certificate association data"
+    /// > to be matched.
+    ///
+    /// <https://datatracker.ietf.org/doc/html/rfc6698#section-2.1>
+    TLSA {
+        name: DnsName,
+        class: DnsClass,
+        ttl: u32,
+        usage: u8,
+        selector: u8,
+        matching_type: u8,
+        data: Vec<u8>,
+    },
+    /// > The RDATA for a SSHFP RR consists of an algorithm number, fingerprint type and the
+    /// > fingerprint of the public host key.
+    ///
+    /// <https://datatracker.ietf.org/doc/html/rfc4255#section-3.1>
+    SSHFP {
+        name: DnsName,
+        class: DnsClass,
+        ttl: u32,
+        algorithm: u8,
+        fingerprint_type: u8,
+        fingerprint: Vec<u8>,
+    },
+    /// > The DNAME RR has mnemonic DNAME and type code 39 (decimal).  It is not class-sensitive.
+    /// >
+    /// > Its RDATA is comprised of a single field, \<target\>, which contains a fully qualified
+    /// > domain name that MUST be sent in uncompressed form
+    ///
+    /// <https://datatracker.ietf.org/doc/html/rfc6672#section-2.1>
+    DNAME {
+        name: DnsName,
+        class: DnsClass,
+        ttl: u32,
+        target: DnsName,
+    },
+    /// > The packet format for the NAPTR record is as follows
+    /// >
+    /// > ```text
+    /// >                                 1  1  1  1  1  1
+    /// >   0  1  2  3  4  5  6  7  8  9  0  1  2  3  4  5
+    /// > +--+--+--+--+--+--+--+--+--+--+--+--+--+--+--+--+
+    /// > |                     ORDER                     |
+    /// > +--+--+--+--+--+--+--+--+--+--+--+--+--+--+--+--+
+    /// > |                   PREFERENCE                  |
+    /// > +--+--+--+--+--+--+--+--+--+--+--+--+--+--+--+--+
+    /// > /                     FLAGS                     /
+    /// > +--+--+--+--+--+--+--+--+--+--+--+--+--+--+--+--+
+    /// > /                   SERVICES                    /
+    /// > +--+--+--+--+--+--+--+--+--+--+--+--+--+--+--+--+
+    /// > /                    REGEXP                     /
+    /// > +--+--+--+--+--+--+--+--+--+--+--+--+--+--+--+--+
+    /// > /                  REPLACEMENT                  /
+    /// > /                                               /
+    /// > +--+--+--+--+--+--+--+--+--+--+--+--+--+--+--+--+
+    /// > ```
+    ///
+    /// <https://datatracker.ietf.org/doc/html/rfc3403#section-4.1>
+    NAPTR {
+        name: DnsName,
+        class: DnsClass,
+        ttl: u32,
+        order: u16,
+        preference: u16,
+        flags: Vec<u8>,
+        service: Vec<u8>,
+        regexp: Vec<u8>,
+        replacement: DnsName,
+    },
+    /// > This document defines a new DNS resource record, called the Uniform Resource Identifier
+    /// > (URI) RR, for publishing mappings from hostnames to URIs.
+    ///
+    /// <https://datatracker.ietf.org/doc/html/rfc7553>
+    URI {
+        name: DnsName,
+        class: DnsClass,
+        ttl: u32,
+        priority: u16,
+        weight: u16,
+        target: String,
+    },
+    /// A record of a type that this library does not know, with its RDATA as opaque bytes.
+    ///
+    /// > To enable new RR types to be deployed without server changes, name servers and resolvers
+    /// > MUST handle RRs of unknown type transparently.  That is, they must treat the RDATA
+    /// > section of such RRs as unstructured binary data, storing and transmitting it without
+    /// > change.
+    ///
+    /// <https://datatracker.ietf.org/doc/html/rfc3597#section-2>
+    Unknown {
+        name: DnsName,
+        class: DnsClass,
+        ttl: u32,
+        typ: DnsType,
+        rdata: Vec<u8>,
+    },
 }
 impl DnsRecord {
+    /// The TTL, in seconds, of records made with the `new_*` constructors.
+    pub const DEFAULT_TTL: u32 = 300;
+
+    /// Reads RDLENGTH and returns `buf` and the length.
+    ///
     /// # Errors
     /// Returns an error when `buf` does not contain a valid resource record.
-    fn read_rdata<const N: usize>(buf: &mut FixedBuf<N>) -> Result<&mut FixedBuf<N>, DnsError> {
+    fn read_rdata<const N: usize>(
+        buf: &mut FixedBuf<N>,
+    ) -> Result<(&mut FixedBuf<N>, usize), DnsError> {
         let len = read_u16_be(buf)?;
         if buf.len() < (len as usize) {
             return Err(DnsError::Truncated);
         }
-        Ok(buf)
+        Ok((buf, len as usize))
     }
 
     /// # Errors
@@ -82,27 +377,36 @@ impl DnsRecord {
         Ok(())
     }
 
+    /// Makes an A record with class IN and TTL [`DEFAULT_TTL`](Self::DEFAULT_TTL).
+    ///
     /// # Errors
-    /// Returns an error when `name` is not a valid DNS name
+    /// Returns an error when `name` is not a valid host name
     /// or `ipv4_addr` is not a valid IPv4 address.
     pub fn new_a(name: &str, ipv4_addr: &str) -> Result<Self, String> {
-        let dns_name = DnsName::new(name)?;
+        let dns_name = DnsName::new_hostname(name)?;
         let ip_addr: IpAddr = ipv4_addr
             .parse()
             .map_err(|e| format!("failed parsing {ipv4_addr:?} as an IP address: {e}"))?;
         match ip_addr {
-            IpAddr::V4(addr) => Ok(Self::A(dns_name, addr)),
+            IpAddr::V4(addr) => Ok(Self::A {
+                name: dns_name,
+                class: DnsClass::Internet,
+                ttl: Self::DEFAULT_TTL,
+                addr,
+            }),
             IpAddr::V6(addr) => Err(format!(
                 "cannot create an A record with ipv6 address {addr:?}"
             )),
         }
     }
 
+    /// Makes an AAAA record with class IN and TTL [`DEFAULT_TTL`](Self::DEFAULT_TTL).
+    ///
     /// # Errors
-    /// Returns an error when `name` is not a valid DNS name
+    /// Returns an error when `name` is not a valid host name
     /// or `ipv6_addr` is not a valid IPv6 address.
     pub fn new_aaaa(name: &str, ipv6_addr: &str) -> Result<Self, String> {
-        let dns_name = DnsName::new(name)?;
+        let dns_name = DnsName::new_hostname(name)?;
         let ip_addr: IpAddr = ipv6_addr
             .parse()
             .map_err(|e| format!("failed parsing {ipv6_addr:?} as an IP address: {e}"))?;
@@ -110,95 +414,1723 @@ impl DnsRecord {
             IpAddr::V4(addr) => Err(format!(
                 "cannot create an AAAA record with ipv4 address {addr:?}"
             )),
-            IpAddr::V6(addr) => Ok(Self::AAAA(dns_name, addr)),
+            IpAddr::V6(addr) => Ok(Self::AAAA {
+                name: dns_name,
+                class: DnsClass::Internet,
+                ttl: Self::DEFAULT_TTL,
+                addr,
+            }),
         }
     }
 
+    /// Makes a CNAME record with class IN and TTL [`DEFAULT_TTL`](Self::DEFAULT_TTL).
+    ///
     /// # Errors
     /// Returns an error when `name` or `target` are not both valid DNS names.
     pub fn new_cname(name: &str, target: &str) -> Result<Self, String> {
         let dns_name = DnsName::new(name)?;
         let dns_name_target = DnsName::new(target)?;
-        Ok(Self::CNAME(dns_name, dns_name_target))
+        Ok(Self::CNAME {
+            name: dns_name,
+            class: DnsClass::Internet,
+            ttl: Self::DEFAULT_TTL,
+            target: dns_name_target,
+        })
+    }
+
+    /// Makes an MX record with class IN and TTL [`DEFAULT_TTL`](Self::DEFAULT_TTL).
+    ///
+    /// # Errors
+    /// Returns an error when `name` is not a valid DNS name
+    /// or `exchange` is not a valid host name.
+    pub fn new_mx(name: &str, preference: u16, exchange: &str) -> Result<Self, String> {
+        Ok(Self::MX {
+            name: DnsName::new(name)?,
+            class: DnsClass::Internet,
+            ttl: Self::DEFAULT_TTL,
+            preference,
+            exchange: DnsName::new_hostname(exchange)?,
+        })
+    }
+
+    /// Makes an NS record with class IN and TTL [`DEFAULT_TTL`](Self::DEFAULT_TTL).
+    /// An NS record at a name below the zone's apex delegates that name to `host`.
+    ///
+    /// # Errors
+    /// Returns an error when `name` is not a valid DNS name
+    /// or `host` is not a valid host name.
+    pub fn new_ns(name: &str, host: &str) -> Result<Self, String> {
+        Ok(Self::NS {
+            name: DnsName::new(name)?,
+            class: DnsClass::Internet,
+            ttl: Self::DEFAULT_TTL,
+            host: DnsName::new_hostname(host)?,
+        })
+    }
+
+    /// Makes an SOA record with class IN and TTL [`DEFAULT_TTL`](Self::DEFAULT_TTL).
+    ///
+    /// `rname` is the zone administrator's mailbox, like `hostmaster@example.com`
+    /// or `hostmaster.example.com`.
+    ///
+    /// Uses refresh 2 hours, retry 1 hour, expire 2 weeks, and minimum 1 hour.
+    /// To use other values, make the `DnsRecord::SOA` variant directly.
+    ///
+    /// # Errors
+    /// Returns an error when `name` is not a valid DNS name,
+    /// `mname` is not a valid host name, or `rname` is not a valid mailbox.
+    pub fn new_soa(name: &str, mname: &str, rname: &str, serial: u32) -> Result<Self, String> {
+        let rname = match rname.rsplit_once('@') {
+            Some((local_part, domain)) => DnsName::new(domain)?
+                .prepend_label(local_part.as_bytes())
+                .map_err(|e| format!("not a valid mailbox: {rname:?}: {e:?}"))?,
+            None => DnsName::new(rname)?,
+        };
+        Ok(Self::SOA {
+            name: DnsName::new(name)?,
+            class: DnsClass::Internet,
+            ttl: Self::DEFAULT_TTL,
+            mname: DnsName::new_hostname(mname)?,
+            rname,
+            serial,
+            refresh: 2 * 60 * 60,
+            retry: 60 * 60,
+            expire: 14 * 24 * 60 * 60,
+            minimum: 60 * 60,
+        })
+    }
+
+    /// Makes a TXT record with class IN and TTL [`DEFAULT_TTL`](Self::DEFAULT_TTL).
+    /// Splits `value` into strings of 255 bytes.
+    ///
+    /// # Errors
+    /// Returns an error when `name` is not a valid DNS name.
+    pub fn new_txt(name: &str, value: &str) -> Result<Self, String> {
+        Self::new_txt_bytes(name, value.as_bytes())
+    }
+
+    /// Makes a TXT record with class IN and TTL [`DEFAULT_TTL`](Self::DEFAULT_TTL).
+    /// Splits `value` into strings of 255 bytes.
+    ///
+    /// # Errors
+    /// Returns an error when `name` is not a valid DNS name.
+    pub fn new_txt_bytes(name: &str, value: &[u8]) -> Result<Self, String> {
+        let mut strings: Vec<Vec<u8>> = value.chunks(255).map(<[u8]>::to_vec).collect();
+        if strings.is_empty() {
+            strings.push(Vec::new());
+        }
+        Ok(Self::TXT {
+            name: DnsName::new(name)?,
+            class: DnsClass::Internet,
+            ttl: Self::DEFAULT_TTL,
+            strings,
+        })
+    }
+
+    /// Makes a PTR record with class IN and TTL [`DEFAULT_TTL`](Self::DEFAULT_TTL).
+    ///
+    /// `name` is a reverse-lookup name like `4.3.2.1.in-addr.arpa`, or an IP address like
+    /// `1.2.3.4` which the function converts with [`DnsName::from_ip`].
+    ///
+    /// Reverse-lookup names may have RFC 2317 labels, like `65.64/26.2.0.192.in-addr.arpa`.
+    /// Other names must be valid for [`DnsName::new`].
+    ///
+    /// # Errors
+    /// Returns an error when `name` is not an IP address, reverse-lookup name,
+    /// or valid DNS name, or `target` is not a valid host name.
+    pub fn new_ptr(name: &str, target: &str) -> Result<Self, String> {
+        let dns_name = match name.parse::<IpAddr>() {
+            Ok(addr) => DnsName::from_ip(addr),
+            Err(_) => match DnsName::with_syntax(name, DnsNameSyntax::Any) {
+                Ok(dns_name) if Self::is_reverse_lookup_name(&dns_name) => dns_name,
+                _ => DnsName::new(name)?,
+            },
+        };
+        Self::new_ptr_with_name(dns_name, target)
+    }
+
+    /// Makes a PTR record for `addr` with class IN and TTL [`DEFAULT_TTL`](Self::DEFAULT_TTL).
+    ///
+    /// # Errors
+    /// Returns an error when `target` is not a valid host name.
+    pub fn new_ptr_for_ip(addr: IpAddr, target: &str) -> Result<Self, String> {
+        Self::new_ptr_with_name(DnsName::from_ip(addr), target)
+    }
+
+    /// Returns true when `name` is a reverse-lookup name, or a name in an RFC 2317 classless
+    /// delegation zone, like `65.64/26.2.0.192.in-addr.arpa`.
+    fn is_reverse_lookup_name(name: &DnsName) -> bool {
+        name.to_ip_prefix().is_some()
+            || (name
+                .labels()
+                .next()
+                .is_some_and(|label| DnsNameSyntax::Service.is_valid_label(label))
+                && name
+                    .parent()
+                    .is_some_and(|parent| parent.to_ip_prefix().is_some()))
+    }
+
+    fn new_ptr_with_name(name: DnsName, target: &str) -> Result<Self, String> {
+        Ok(Self::PTR {
+            name,
+            class: DnsClass::Internet,
+            ttl: Self::DEFAULT_TTL,
+            target: DnsName::new_hostname(target)?,
+        })
+    }
+
+    /// Makes an SRV record with class IN and TTL [`DEFAULT_TTL`](Self::DEFAULT_TTL).
+    ///
+    /// `name` is like `_mqtt._tcp.example.com`.
+    /// `target` is a host name, or `.` when the service is not available.
+    ///
+    /// # Errors
+    /// Returns an error when `name` is not a valid DNS name
+    /// or `target` is not a valid host name.
+    pub fn new_srv(
+        name: &str,
+        priority: u16,
+        weight: u16,
+        port: u16,
+        target: &str,
+    ) -> Result<Self, String> {
+        let target = DnsName::new_hostname(target)?;
+        Ok(Self::SRV {
+            name: DnsName::new(name)?,
+            class: DnsClass::Internet,
+            ttl: Self::DEFAULT_TTL,
+            priority,
+            weight,
+            port,
+            target,
+        })
+    }
+
+    /// The Issuer Critical Flag of CAA records.
+    pub const CAA_CRITICAL: u8 = 0x80;
+
+    fn is_valid_caa_tag(tag: &[u8]) -> bool {
+        !tag.is_empty() && tag.iter().all(u8::is_ascii_alphanumeric)
+    }
+
+    /// Makes a CAA record with class IN and TTL [`DEFAULT_TTL`](Self::DEFAULT_TTL).
+    ///
+    /// `flags` is 0 or [`CAA_CRITICAL`](Self::CAA_CRITICAL).
+    /// `tag` is like `issue`, `issuewild`, or `iodef`.
+    ///
+    /// > To ensure compatibility with future extensions to CAA, DNS records compliant with this
+    /// > version of the CAA specification MUST clear (set to "0") all reserved flag bits.
+    /// >
+    /// > ...
+    /// >
+    /// > Tag Length:  A single octet containing an unsigned integer specifying the tag length in
+    /// > octets.  The tag length MUST be at least 1 and SHOULD be no more than 15.
+    ///
+    /// <https://datatracker.ietf.org/doc/html/rfc8659#section-4.1>
+    ///
+    /// # Errors
+    /// Returns an error when `name` is not a valid DNS name, `flags` has reserved bits set,
+    /// or `tag` is empty, longer than 15 bytes, or contains characters other than ASCII letters
+    /// and digits.
+    pub fn new_caa(name: &str, flags: u8, tag: &str, value: &str) -> Result<Self, String> {
+        Self::new_caa_bytes(name, flags, tag.as_bytes(), value.as_bytes())
+    }
+
+    fn new_caa_bytes(name: &str, flags: u8, tag: &[u8], value: &[u8]) -> Result<Self, String> {
+        if flags & !Self::CAA_CRITICAL != 0 {
+            return Err(format!("CAA flags has reserved bits set: {flags}"));
+        }
+        let tag = std::str::from_utf8(tag)
+            .ok()
+            .filter(|tag| tag.len() <= 15 && Self::is_valid_caa_tag(tag.as_bytes()))
+            .ok_or_else(|| format!("not a valid CAA tag: {:?}", String::from_utf8_lossy(tag)))?;
+        Ok(Self::CAA {
+            name: DnsName::new(name)?,
+            class: DnsClass::Internet,
+            ttl: Self::DEFAULT_TTL,
+            flags,
+            tag: tag.to_string(),
+            value: value.to_vec(),
+        })
+    }
+
+    /// Makes a CAA record from presentation format `rdata`, like `0 issue "letsencrypt.org"`.
+    ///
+    /// > The Presentation Format of CAA RRs is as follows:
+    /// >
+    /// > `CAA <flags> <tag> <value>`
+    /// >
+    /// > Where:
+    /// >
+    /// > - `<flags>` is an unsigned integer between 0 and 255.
+    /// > - `<tag>` is a non-zero-length sequence of ASCII letters and numbers in lowercase.
+    /// > - `<value>` is the value field, expressed as either (1) a contiguous set of characters
+    /// >   without interior spaces or (2) a quoted string.
+    ///
+    /// <https://datatracker.ietf.org/doc/html/rfc8659#section-4.1.1>
+    ///
+    /// # Errors
+    /// Returns an error when `rdata` is not valid CAA presentation format,
+    /// or for the reasons that [`new_caa`](Self::new_caa) does.
+    pub fn parse_caa(name: &str, rdata: &str) -> Result<Self, String> {
+        match split_fields(rdata)?.as_slice() {
+            [flags, tag, value] => {
+                Self::new_caa_bytes(name, parse_number(flags, "CAA flags")?, tag, value)
+            }
+            _ => Err(format!("not valid CAA presentation format: {rdata:?}")),
+        }
+    }
+
+    /// Checks the fields of an SVCB or HTTPS record and puts `params` in order.
+    fn svcb_fields(
+        priority: u16,
+        target: &str,
+        mut params: Vec<DnsSvcParam>,
+    ) -> Result<(DnsName, Vec<DnsSvcParam>), String> {
+        // > In AliasMode, records SHOULD NOT include any SvcParams
+        // https://datatracker.ietf.org/doc/html/rfc9460#section-2.4.2
+        if priority == 0 && !params.is_empty() {
+            return Err("AliasMode records with priority 0 cannot have SvcParams".to_string());
+        }
+        DnsSvcParam::sort(&mut params);
+        DnsSvcParam::validate(&params)?;
+        let target = DnsName::new(target)?;
+        Ok((target, params))
+    }
+
+    /// Makes an SVCB record with class IN and TTL [`DEFAULT_TTL`](Self::DEFAULT_TTL).
+    ///
+    /// `priority` 0 makes an AliasMode record, which must have no `params`.
+    /// `target` is a DNS name, or `.` for the record's own name in ServiceMode.
+    /// Puts `params` in the order that RFC 9460 requires.
+    ///
+    /// # Errors
+    /// Returns an error when `name` or `target` is not a valid DNS name,
+    /// or `params` break the rules of RFC 9460.
+    pub fn new_svcb(
+        name: &str,
+        priority: u16,
+        target: &str,
+        params: Vec<DnsSvcParam>,
+    ) -> Result<Self, String> {
+        let (target, params) = Self::svcb_fields(priority, target, params)?;
+        Ok(Self::SVCB {
+            name: DnsName::new(name)?,
+            class: DnsClass::Internet,
+            ttl: Self::DEFAULT_TTL,
+            priority,
+            target,
+            params,
+        })
+    }
+
+    /// Makes an HTTPS record with class IN and TTL [`DEFAULT_TTL`](Self::DEFAULT_TTL).
+    /// See [`new_svcb`](Self::new_svcb).
+    ///
+    /// # Errors
+    /// Returns an error when `name` or `target` is not a valid DNS name,
+    /// or `params` break the rules of RFC 9460.
+    pub fn new_https(
+        name: &str,
+        priority: u16,
+        target: &str,
+        params: Vec<DnsSvcParam>,
+    ) -> Result<Self, String> {
+        let (target, params) = Self::svcb_fields(priority, target, params)?;
+        Ok(Self::HTTPS {
+            name: DnsName::new(name)?,
+            class: DnsClass::Internet,
+            ttl: Self::DEFAULT_TTL,
+            priority,
+            target,
+            params,
+        })
+    }
+
+    /// TLSA certificate usage: CA constraint, validated with PKIX.
+    pub const TLSA_USAGE_PKIX_TA: u8 = 0;
+    /// TLSA certificate usage: service certificate constraint, validated with PKIX.
+    pub const TLSA_USAGE_PKIX_EE: u8 = 1;
+    /// TLSA certificate usage: trust anchor assertion.
+    pub const TLSA_USAGE_DANE_TA: u8 = 2;
+    /// TLSA certificate usage: domain-issued certificate.
+    /// This is the usual usage for SMTP servers.
+    ///
+    /// <https://datatracker.ietf.org/doc/html/rfc7672#section-3.1.1>
+    pub const TLSA_USAGE_DANE_EE: u8 = 3;
+    /// TLSA selector: the full DER-encoded certificate.
+    pub const TLSA_SELECTOR_CERT: u8 = 0;
+    /// TLSA selector: the DER-encoded `SubjectPublicKeyInfo` of the certificate.
+    pub const TLSA_SELECTOR_SPKI: u8 = 1;
+    /// TLSA matching type: the selected content itself.
+    pub const TLSA_MATCHING_FULL: u8 = 0;
+    /// TLSA matching type: the SHA-256 hash of the selected content.
+    pub const TLSA_MATCHING_SHA256: u8 = 1;
+    /// TLSA matching type: the SHA-512 hash of the selected content.
+    pub const TLSA_MATCHING_SHA512: u8 = 2;
+
+    /// Makes a TLSA record with class IN and TTL [`DEFAULT_TTL`](Self::DEFAULT_TTL).
+    ///
+    /// `name` is like `_25._tcp.mail.example.com`.
+    /// `data` is the certificate association data, a hash when `matching_type` is
+    /// [`TLSA_MATCHING_SHA256`](Self::TLSA_MATCHING_SHA256) or
+    /// [`TLSA_MATCHING_SHA512`](Self::TLSA_MATCHING_SHA512).
+    ///
+    /// <https://datatracker.ietf.org/doc/html/rfc6698#section-3>
+    ///
+    /// # Errors
+    /// Returns an error when `name` is not a valid DNS name, `data` is empty,
+    /// or `data` is not the length of the hash that `matching_type` specifies.
+    pub fn new_tlsa(
+        name: &str,
+        usage: u8,
+        selector: u8,
+        matching_type: u8,
+        data: &[u8],
+    ) -> Result<Self, String> {
+        let expected_len = match matching_type {
+            Self::TLSA_MATCHING_SHA256 => Some(32),
+            Self::TLSA_MATCHING_SHA512 => Some(64),
+            _ => None,
+        };
+        if data.is_empty() || expected_len.is_some_and(|len| len != data.len()) {
+            return Err(format!(
+                "TLSA data has wrong length {} for matching type {matching_type}",
+                data.len()
+            ));
+        }
+        Ok(Self::TLSA {
+            name: DnsName::new(name)?,
+            class: DnsClass::Internet,
+            ttl: Self::DEFAULT_TTL,
+            usage,
+            selector,
+            matching_type,
+            data: data.to_vec(),
+        })
+    }
+
+    /// Makes a TLSA record that matches a certificate, with class IN and TTL
+    /// [`DEFAULT_TTL`](Self::DEFAULT_TTL).
+    ///
+    /// `certificate` is the DER-encoded X.509 certificate.
+    /// Selects the whole certificate when `selector` is
+    /// [`TLSA_SELECTOR_CERT`](Self::TLSA_SELECTOR_CERT), or its `SubjectPublicKeyInfo` when
+    /// `selector` is [`TLSA_SELECTOR_SPKI`](Self::TLSA_SELECTOR_SPKI).
+    /// Hashes the selected bytes with SHA-256 or SHA-512, or uses them unchanged,
+    /// as `matching_type` specifies.
+    ///
+    /// For SMTP servers, use [`TLSA_USAGE_DANE_EE`](Self::TLSA_USAGE_DANE_EE),
+    /// [`TLSA_SELECTOR_SPKI`](Self::TLSA_SELECTOR_SPKI), and
+    /// [`TLSA_MATCHING_SHA256`](Self::TLSA_MATCHING_SHA256).
+    ///
+    /// <https://datatracker.ietf.org/doc/html/rfc7671#section-5.1>
+    ///
+    /// # Errors
+    /// Returns an error when `name` is not a valid DNS name, `certificate` is empty,
+    /// `selector` is not one of the `TLSA_SELECTOR_*` values,
+    /// `matching_type` is not one of the `TLSA_MATCHING_*` values,
+    /// or `selector` is [`TLSA_SELECTOR_SPKI`](Self::TLSA_SELECTOR_SPKI) and `certificate`
+    /// is not a DER-encoded X.509 certificate.
+    #[cfg(feature = "sha2")]
+    pub fn new_tlsa_from_der(
+        name: &str,
+        usage: u8,
+        selector: u8,
+        matching_type: u8,
+        certificate: &[u8],
+    ) -> Result<Self, String> {
+        use sha2::{Digest, Sha256, Sha512};
+        let selected = match selector {
+            Self::TLSA_SELECTOR_CERT => certificate,
+            Self::TLSA_SELECTOR_SPKI => Self::certificate_spki(certificate)
+                .ok_or_else(|| "not a valid DER-encoded X.509 certificate".to_string())?,
+            _ => return Err(format!("unknown TLSA selector {selector}")),
+        };
+        let data = match matching_type {
+            Self::TLSA_MATCHING_FULL => selected.to_vec(),
+            Self::TLSA_MATCHING_SHA256 if !selected.is_empty() => Sha256::digest(selected).to_vec(),
+            Self::TLSA_MATCHING_SHA512 if !selected.is_empty() => Sha512::digest(selected).to_vec(),
+            Self::TLSA_MATCHING_SHA256 | Self::TLSA_MATCHING_SHA512 => Vec::new(),
+            _ => return Err(format!("unknown TLSA matching type {matching_type}")),
+        };
+        Self::new_tlsa(name, usage, selector, matching_type, &data)
+    }
+
+    /// Reads the DER element at the start of `der` and advances `der` past it.
+    /// Returns the element's tag, its contents, and its whole encoding.
+    ///
+    /// Supports only single-octet tags and definite lengths, which is all that X.509
+    /// certificates use.
+    ///
+    /// <https://www.itu.int/rec/T-REC-X.690>
+    #[cfg(feature = "sha2")]
+    fn read_der_element<'a>(der: &mut &'a [u8]) -> Option<(u8, &'a [u8], &'a [u8])> {
+        let all: &'a [u8] = der;
+        let (&tag, rest) = all.split_first()?;
+        if tag & 0x1F == 0x1F {
+            return None;
+        }
+        let (&first, mut rest) = rest.split_first()?;
+        let len = if first < 0x80 {
+            usize::from(first)
+        } else {
+            let num_octets = usize::from(first & 0x7F);
+            if num_octets == 0 || num_octets > 4 || rest.len() < num_octets {
+                return None;
+            }
+            let (len_octets, after) = rest.split_at(num_octets);
+            rest = after;
+            len_octets
+                .iter()
+                .fold(0_usize, |len, octet| (len << 8) | usize::from(*octet))
+        };
+        if rest.len() < len {
+            return None;
+        }
+        let header_len = all.len() - rest.len();
+        let (encoded, after) = all.split_at(header_len + len);
+        *der = after;
+        Some((tag, &encoded[header_len..], encoded))
+    }
+
+    /// Returns the DER-encoded `SubjectPublicKeyInfo` of DER-encoded X.509 `certificate`.
+    ///
+    /// > ```text
+    /// > Certificate  ::=  SEQUENCE  {
+    /// >      tbsCertificate       TBSCertificate,
+    /// >      ...
+    /// > TBSCertificate  ::=  SEQUENCE  {
+    /// >      version         [0]  EXPLICIT Version DEFAULT v1,
+    /// >      serialNumber         CertificateSerialNumber,
+    /// >      signature            AlgorithmIdentifier,
+    /// >      issuer               Name,
+    /// >      validity             Validity,
+    /// >      subject              Name,
+    /// >      subjectPublicKeyInfo SubjectPublicKeyInfo,
+    /// >      ...
+    /// > ```
+    ///
+    /// <https://datatracker.ietf.org/doc/html/rfc5280#section-4.1>
+    #[cfg(feature = "sha2")]
+    fn certificate_spki(mut certificate: &[u8]) -> Option<&[u8]> {
+        const SEQUENCE: u8 = 0x30;
+        const VERSION: u8 = 0xA0;
+        let (SEQUENCE, mut certificate_contents, _) = Self::read_der_element(&mut certificate)?
+        else {
+            return None;
+        };
+        if !certificate.is_empty() {
+            return None;
+        }
+        let (SEQUENCE, mut tbs, _) = Self::read_der_element(&mut certificate_contents)? else {
+            return None;
+        };
+        let (tag, _, _) = Self::read_der_element(&mut tbs)?;
+        if tag == VERSION {
+            // serialNumber
+            Self::read_der_element(&mut tbs)?;
+        }
+        // signature, issuer, validity, subject
+        for _ in 0..4 {
+            Self::read_der_element(&mut tbs)?;
+        }
+        match Self::read_der_element(&mut tbs)? {
+            (SEQUENCE, _, spki) => Some(spki),
+            _ => None,
+        }
+    }
+
+    /// Makes a TLSA record from presentation format `rdata`, like `3 1 1 0123...`.
+    ///
+    /// > The certificate association data field MUST be represented as a string of hexadecimal
+    /// > characters.  Whitespace is allowed within the string of hexadecimal characters ...
+    ///
+    /// <https://datatracker.ietf.org/doc/html/rfc6698#section-2.2>
+    ///
+    /// # Errors
+    /// Returns an error when `rdata` is not valid TLSA presentation format,
+    /// or for the reasons that [`new_tlsa`](Self::new_tlsa) does.
+    pub fn parse_tlsa(name: &str, rdata: &str) -> Result<Self, String> {
+        match split_fields(rdata)?.as_slice() {
+            [usage, selector, matching_type, words @ ..] => {
+                let mut data: Vec<u8> = Vec::new();
+                for word in words {
+                    data.extend(parse_hex(word, "TLSA data")?);
+                }
+                Self::new_tlsa(
+                    name,
+                    parse_number(usage, "TLSA usage")?,
+                    parse_number(selector, "TLSA selector")?,
+                    parse_number(matching_type, "TLSA matching type")?,
+                    &data,
+                )
+            }
+            _ => Err(format!("not valid TLSA presentation format: {rdata:?}")),
+        }
+    }
+
+    /// SSHFP algorithm: RSA
+    pub const SSHFP_ALGORITHM_RSA: u8 = 1;
+    /// SSHFP algorithm: DSA
+    pub const SSHFP_ALGORITHM_DSA: u8 = 2;
+    /// SSHFP algorithm: ECDSA
+    ///
+    /// <https://datatracker.ietf.org/doc/html/rfc6594>
+    pub const SSHFP_ALGORITHM_ECDSA: u8 = 3;
+    /// SSHFP algorithm: Ed25519
+    ///
+    /// <https://datatracker.ietf.org/doc/html/rfc7479>
+    pub const SSHFP_ALGORITHM_ED25519: u8 = 4;
+    /// SSHFP algorithm: Ed448
+    ///
+    /// <https://datatracker.ietf.org/doc/html/rfc8709>
+    pub const SSHFP_ALGORITHM_ED448: u8 = 6;
+    /// SSHFP fingerprint type: SHA-1
+    pub const SSHFP_TYPE_SHA1: u8 = 1;
+    /// SSHFP fingerprint type: SHA-256
+    ///
+    /// <https://datatracker.ietf.org/doc/html/rfc6594>
+    pub const SSHFP_TYPE_SHA256: u8 = 2;
+
+    /// Makes an SSHFP record with class IN and TTL [`DEFAULT_TTL`](Self::DEFAULT_TTL).
+    ///
+    /// `algorithm` is one of the `SSHFP_ALGORITHM_*` values.
+    /// `fingerprint` is a hash of the public key, as `fingerprint_type` specifies.
+    ///
+    /// # Errors
+    /// Returns an error when `name` is not a valid DNS name, `fingerprint` is empty,
+    /// or `fingerprint` is not the length of the hash that `fingerprint_type` specifies.
+    pub fn new_sshfp(
+        name: &str,
+        algorithm: u8,
+        fingerprint_type: u8,
+        fingerprint: &[u8],
+    ) -> Result<Self, String> {
+        let expected_len = match fingerprint_type {
+            Self::SSHFP_TYPE_SHA1 => Some(20),
+            Self::SSHFP_TYPE_SHA256 => Some(32),
+            _ => None,
+        };
+        if fingerprint.is_empty() || expected_len.is_some_and(|len| len != fingerprint.len()) {
+            return Err(format!(
+                "SSHFP fingerprint has wrong length {} for fingerprint type {fingerprint_type}",
+                fingerprint.len()
+            ));
+        }
+        Ok(Self::SSHFP {
+            name: DnsName::new(name)?,
+            class: DnsClass::Internet,
+            ttl: Self::DEFAULT_TTL,
+            algorithm,
+            fingerprint_type,
+            fingerprint: fingerprint.to_vec(),
+        })
+    }
+
+    /// Makes SSHFP records with the SHA-1 and SHA-256 fingerprints of an OpenSSH public key,
+    /// with class IN and TTL [`DEFAULT_TTL`](Self::DEFAULT_TTL).
+    ///
+    /// `public_key` is a line from a `.pub` file, like
+    /// `ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAA... root@host`.
+    /// Supports `ssh-ed25519`, `ecdsa-sha2-nistp256`, and `ssh-rsa` keys.
+    ///
+    /// > The fingerprint is calculated over the public key blob as described in [7].
+    ///
+    /// <https://datatracker.ietf.org/doc/html/rfc4255#section-3.1.3>
+    ///
+    /// # Errors
+    /// Returns an error when `name` is not a valid DNS name,
+    /// or `public_key` is not a valid OpenSSH public key of a supported type.
+    #[cfg(all(feature = "sha1", feature = "sha2"))]
+    pub fn new_sshfp_from_public_key(name: &str, public_key: &str) -> Result<Vec<Self>, String> {
+        use sha2::Digest;
+        let err = || format!("not a valid OpenSSH public key: {public_key:?}");
+        let mut words = public_key.split_ascii_whitespace();
+        let (Some(key_type), Some(encoded)) = (words.next(), words.next()) else {
+            return Err(err());
+        };
+        let algorithm = match key_type {
+            "ssh-rsa" => Self::SSHFP_ALGORITHM_RSA,
+            "ecdsa-sha2-nistp256" => Self::SSHFP_ALGORITHM_ECDSA,
+            "ssh-ed25519" => Self::SSHFP_ALGORITHM_ED25519,
+            _ => return Err(format!("unsupported SSH public key type: {key_type:?}")),
+        };
+        let blob = base64_decode(encoded.as_bytes()).ok_or_else(err)?;
+        // The blob starts with the key type as an SSH string.
+        // https://datatracker.ietf.org/doc/html/rfc4253#section-6.6
+        let blob_key_type = blob
+            .get(..4)
+            .and_then(|len| usize::try_from(u32::from_be_bytes(len.try_into().ok()?)).ok())
+            .and_then(|len| blob.get(4..4 + len));
+        if blob_key_type != Some(key_type.as_bytes()) {
+            return Err(err());
+        }
+        Ok(vec![
+            Self::new_sshfp(
+                name,
+                algorithm,
+                Self::SSHFP_TYPE_SHA1,
+                &sha1::Sha1::digest(&blob),
+            )?,
+            Self::new_sshfp(
+                name,
+                algorithm,
+                Self::SSHFP_TYPE_SHA256,
+                &sha2::Sha256::digest(&blob),
+            )?,
+        ])
+    }
+
+    /// Makes an SSHFP record from presentation format `rdata`, like `4 2 0123...`.
+    ///
+    /// > The RDATA of the presentation format of the SSHFP resource record consists of two
+    /// > numbers (algorithm and fingerprint type) followed by the fingerprint itself, presented
+    /// > in hex
+    ///
+    /// <https://datatracker.ietf.org/doc/html/rfc4255#section-3.2>
+    ///
+    /// # Errors
+    /// Returns an error when `rdata` is not valid SSHFP presentation format,
+    /// or for the reasons that [`new_sshfp`](Self::new_sshfp) does.
+    pub fn parse_sshfp(name: &str, rdata: &str) -> Result<Self, String> {
+        match split_fields(rdata)?.as_slice() {
+            [algorithm, fingerprint_type, words @ ..] => {
+                let mut fingerprint: Vec<u8> = Vec::new();
+                for word in words {
+                    fingerprint.extend(parse_hex(word, "SSHFP fingerprint")?);
+                }
+                Self::new_sshfp(
+                    name,
+                    parse_number(algorithm, "SSHFP algorithm")?,
+                    parse_number(fingerprint_type, "SSHFP fingerprint type")?,
+                    &fingerprint,
+                )
+            }
+            _ => Err(format!("not valid SSHFP presentation format: {rdata:?}")),
+        }
+    }
+
+    /// Makes a DNAME record with class IN and TTL [`DEFAULT_TTL`](Self::DEFAULT_TTL).
+    ///
+    /// Lookups of names below `name` get a CNAME to the same name below `target`,
+    /// like `www.new.example.com` for `www.old.example.com` when `name` is `old.example.com`
+    /// and `target` is `new.example.com`.  Lookups of `name` itself are unaffected.
+    ///
+    /// # Errors
+    /// Returns an error when `name` or `target` is not a valid DNS name.
+    pub fn new_dname(name: &str, target: &str) -> Result<Self, String> {
+        Ok(Self::DNAME {
+            name: DnsName::new(name)?,
+            class: DnsClass::Internet,
+            ttl: Self::DEFAULT_TTL,
+            target: DnsName::new(target)?,
+        })
+    }
+
+    /// Checks the syntax of a NAPTR REGEXP field.
+    ///
+    /// > ```text
+    /// > subst-expr   = delim-char  ere  delim-char  repl  delim-char  *flags
+    /// > delim-char   = "/" / "!" / <Any octet not in 'POS-DIGIT' or 'flags'>
+    /// >                    ; All occurrences of a delim_char in a subst_expr
+    /// >                    ; must be escaped
+    /// > ```
+    ///
+    /// <https://datatracker.ietf.org/doc/html/rfc3402#section-3.2>
+    ///
+    /// Also rejects `\` as the delimiter and an empty `ere`.
+    fn is_valid_naptr_regexp(regexp: &str) -> bool {
+        let mut chars = regexp.chars();
+        let Some(delim) = chars.next() else {
+            return true;
+        };
+        if matches!(delim, '1'..='9' | 'i' | '\\') {
+            return false;
+        }
+        let mut parts: Vec<String> = Vec::new();
+        let mut part = String::new();
+        while let Some(c) = chars.next() {
+            if c == delim {
+                parts.push(std::mem::take(&mut part));
+            } else if c == '\\' {
+                let Some(escaped) = chars.next() else {
+                    return false;
+                };
+                part.push(c);
+                part.push(escaped);
+            } else {
+                part.push(c);
+            }
+        }
+        parts.push(part);
+        match parts.as_slice() {
+            [ere, _repl, flags] => !ere.is_empty() && (flags.is_empty() || flags == "i"),
+            _ => false,
+        }
+    }
+
+    /// Makes a NAPTR record with class IN and TTL [`DEFAULT_TTL`](Self::DEFAULT_TTL).
+    ///
+    /// `flags` is like `u` or `s`.  `service` is like `E2U+sip`.
+    /// `regexp` is a substitution expression, like `!^.*$!sip:info@example.com!`, or empty.
+    /// `replacement` is a DNS name, or `.` when `regexp` is not empty.
+    ///
+    /// > The Replacement field and Regexp field together make up the Substitution Expression in
+    /// > the DDDS Algorithm. ... The fields are also mutually exclusive.
+    ///
+    /// <https://datatracker.ietf.org/doc/html/rfc3403#section-4.1>
+    ///
+    /// # Errors
+    /// Returns an error when `name` or `replacement` is not a valid DNS name,
+    /// `flags` contains characters other than ASCII letters and digits,
+    /// `flags`, `service`, or `regexp` is longer than 255 bytes,
+    /// `regexp` is not a valid substitution expression,
+    /// or both `regexp` and `replacement` are present.
+    pub fn new_naptr(
+        name: &str,
+        order: u16,
+        preference: u16,
+        flags: &str,
+        service: &str,
+        regexp: &str,
+        replacement: &str,
+    ) -> Result<Self, String> {
+        if !flags.bytes().all(|b| b.is_ascii_alphanumeric()) {
+            return Err(format!(
+                "NAPTR flags must be ASCII letters and digits: {flags:?}"
+            ));
+        }
+        for field in [flags, service, regexp] {
+            if field.len() > 255 {
+                return Err(format!("NAPTR field is longer than 255 bytes: {field:?}"));
+            }
+        }
+        if !Self::is_valid_naptr_regexp(regexp) {
+            return Err(format!("not a valid NAPTR regexp: {regexp:?}"));
+        }
+        let replacement = DnsName::new(replacement)?;
+        if !regexp.is_empty() && !replacement.is_root() {
+            return Err("NAPTR record cannot have both regexp and replacement".to_string());
+        }
+        Ok(Self::NAPTR {
+            name: DnsName::new(name)?,
+            class: DnsClass::Internet,
+            ttl: Self::DEFAULT_TTL,
+            order,
+            preference,
+            flags: flags.as_bytes().to_vec(),
+            service: service.as_bytes().to_vec(),
+            regexp: regexp.as_bytes().to_vec(),
+            replacement,
+        })
+    }
+
+    /// Makes a NAPTR record from presentation format `rdata`, like
+    /// `100 10 "u" "E2U+sip" "!^.*$!sip:info@example.com!" .`.
+    ///
+    /// # Errors
+    /// Returns an error when `rdata` is not valid NAPTR presentation format,
+    /// or for the reasons that [`new_naptr`](Self::new_naptr) does.
+    pub fn parse_naptr(name: &str, rdata: &str) -> Result<Self, String> {
+        let utf8 = |field: &[u8]| {
+            String::from_utf8(field.to_vec())
+                .map_err(|_| format!("not valid NAPTR presentation format: {rdata:?}"))
+        };
+        match split_fields(rdata)?.as_slice() {
+            [order, preference, flags, service, regexp, replacement] => Self::new_naptr(
+                name,
+                parse_number(order, "NAPTR order")?,
+                parse_number(preference, "NAPTR preference")?,
+                &utf8(flags)?,
+                &utf8(service)?,
+                &utf8(regexp)?,
+                &utf8(replacement)?,
+            ),
+            _ => Err(format!("not valid NAPTR presentation format: {rdata:?}")),
+        }
+    }
+
+    /// Returns true when `target` starts with a URI scheme, like `https:`.
+    ///
+    /// > `scheme = ALPHA *( ALPHA / DIGIT / "+" / "-" / "." )`
+    ///
+    /// <https://datatracker.ietf.org/doc/html/rfc3986#section-3.1>
+    fn has_uri_scheme(target: &str) -> bool {
+        target.split_once(':').is_some_and(|(scheme, _)| {
+            scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+                && scheme
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+        })
+    }
+
+    /// Makes a URI record with class IN and TTL [`DEFAULT_TTL`](Self::DEFAULT_TTL).
+    ///
+    /// `name` is like `_sip._udp.example.com`.
+    /// `target` is a URI, like `sip:info@example.com`.
+    ///
+    /// # Errors
+    /// Returns an error when `name` is not a valid DNS name or `target` does not start with a
+    /// URI scheme.
+    pub fn new_uri(name: &str, priority: u16, weight: u16, target: &str) -> Result<Self, String> {
+        if !Self::has_uri_scheme(target) {
+            return Err(format!("not a valid URI: {target:?}"));
+        }
+        Ok(Self::URI {
+            name: DnsName::new(name)?,
+            class: DnsClass::Internet,
+            ttl: Self::DEFAULT_TTL,
+            priority,
+            weight,
+            target: target.to_string(),
+        })
+    }
+
+    /// Makes a URI record from presentation format `rdata`, like
+    /// `10 1 "ftp://ftp1.example.com/public"`.
+    ///
+    /// <https://datatracker.ietf.org/doc/html/rfc7553#section-4.4>
+    ///
+    /// # Errors
+    /// Returns an error when `rdata` is not valid URI presentation format,
+    /// or for the reasons that [`new_uri`](Self::new_uri) does.
+    pub fn parse_uri(name: &str, rdata: &str) -> Result<Self, String> {
+        match split_fields(rdata)?.as_slice() {
+            [priority, weight, target] => Self::new_uri(
+                name,
+                parse_number(priority, "URI priority")?,
+                parse_number(weight, "URI weight")?,
+                std::str::from_utf8(target)
+                    .map_err(|_| format!("not valid URI presentation format: {rdata:?}"))?,
+            ),
+            _ => Err(format!("not valid URI presentation format: {rdata:?}")),
+        }
+    }
+
+    /// Makes a record of an unknown type, with class IN and TTL
+    /// [`DEFAULT_TTL`](Self::DEFAULT_TTL).
+    ///
+    /// `typ` is a type mnemonic in the `TYPEnnn` form, like `TYPE65280`.
+    /// `rdata` is in the generic presentation format, like `\# 4 0A000001`.
+    ///
+    /// > The RDATA section of an RR of unknown type is represented as a sequence of white space
+    /// > separated words as follows:
+    /// >
+    /// > - The special token `\#` (a backslash immediately followed by a hash sign), which
+    /// >   identifies the RDATA as having the generic encoding defined herein rather than a
+    /// >   traditional type-specific encoding.
+    /// > - An unsigned decimal integer specifying the RDATA length in octets.
+    /// > - Zero or more words of hexadecimal data encoding the actual RDATA field, each
+    /// >   containing an even number of hexadecimal digits.
+    ///
+    /// <https://datatracker.ietf.org/doc/html/rfc3597#section-5>
+    ///
+    /// # Errors
+    /// Returns an error when `name` is not a valid DNS name,
+    /// `typ` is not in the `TYPEnnn` form or is a type that this library knows,
+    /// or `rdata` is not valid generic presentation format.
+    pub fn new_unknown(name: &str, typ: &str, rdata: &str) -> Result<Self, String> {
+        let typ = match DnsType::parse(typ)? {
+            typ @ DnsType::Unknown(_) => typ,
+            typ => return Err(format!("{typ} is a known type, use its constructor")),
+        };
+        let err = || format!("not valid generic RDATA presentation format: {rdata:?}");
+        let rest = rdata
+            .trim_start()
+            .strip_prefix("\\#")
+            .filter(|rest| rest.is_empty() || rest.starts_with(|c: char| c.is_ascii_whitespace()))
+            .ok_or_else(err)?;
+        let fields = split_fields(rest)?;
+        let (len, words) = fields.split_first().ok_or_else(err)?;
+        let len: u16 = parse_number(len, "RDATA length")?;
+        let mut bytes: Vec<u8> = Vec::new();
+        for word in words {
+            bytes.extend(parse_hex(word, "RDATA")?);
+        }
+        if bytes.len() != usize::from(len) {
+            return Err(format!(
+                "RDATA has {} bytes, not {len}: {rdata:?}",
+                bytes.len()
+            ));
+        }
+        Ok(Self::Unknown {
+            name: DnsName::new(name)?,
+            class: DnsClass::Internet,
+            ttl: Self::DEFAULT_TTL,
+            typ,
+            rdata: bytes,
+        })
+    }
+
+    /// Returns a copy of the record with TTL `ttl` seconds.
+    ///
+    /// > TTL: a 32 bit unsigned integer that specifies the time interval (in seconds) that the
+    /// > resource record may be cached before it should be discarded.
+    ///
+    /// <https://datatracker.ietf.org/doc/html/rfc1035#section-4.1.3>
+    #[must_use]
+    pub fn with_ttl(mut self, ttl: u32) -> Self {
+        *self.fields_mut().2 = ttl;
+        self
+    }
+
+    /// Returns a copy of the record with class `class`.
+    #[must_use]
+    pub fn with_class(mut self, class: DnsClass) -> Self {
+        *self.fields_mut().1 = class;
+        self
+    }
+
+    /// Returns a copy of the record with owner name `name`.
+    #[must_use]
+    pub(crate) fn with_name(mut self, name: DnsName) -> Self {
+        *self.fields_mut().0 = name;
+        self
+    }
+
+    fn fields_mut(&mut self) -> (&mut DnsName, &mut DnsClass, &mut u32) {
+        match self {
+            DnsRecord::A {
+                name, class, ttl, ..
+            }
+            | DnsRecord::AAAA {
+                name, class, ttl, ..
+            }
+            | DnsRecord::CNAME {
+                name, class, ttl, ..
+            }
+            | DnsRecord::MX {
+                name, class, ttl, ..
+            }
+            | DnsRecord::NS {
+                name, class, ttl, ..
+            }
+            | DnsRecord::SOA {
+                name, class, ttl, ..
+            }
+            | DnsRecord::TXT {
+                name, class, ttl, ..
+            }
+            | DnsRecord::PTR {
+                name, class, ttl, ..
+            }
+            | DnsRecord::SRV {
+                name, class, ttl, ..
+            }
+            | DnsRecord::CAA {
+                name, class, ttl, ..
+            }
+            | DnsRecord::SVCB {
+                name, class, ttl, ..
+            }
+            | DnsRecord::HTTPS {
+                name, class, ttl, ..
+            }
+            | DnsRecord::TLSA {
+                name, class, ttl, ..
+            }
+            | DnsRecord::SSHFP {
+                name, class, ttl, ..
+            }
+            | DnsRecord::DNAME {
+                name, class, ttl, ..
+            }
+            | DnsRecord::NAPTR {
+                name, class, ttl, ..
+            }
+            | DnsRecord::URI {
+                name, class, ttl, ..
+            }
+            | DnsRecord::Unknown {
+                name, class, ttl, ..
+            } => (name, class, ttl),
+        }
     }
 
     #[must_use]
     pub fn name(&self) -> &DnsName {
         match self {
-            DnsRecord::A(dns_name, _)
-            | DnsRecord::AAAA(dns_name, _)
-            | DnsRecord::CNAME(dns_name, _)
-            | DnsRecord::Unknown(dns_name, _) => dns_name,
+            DnsRecord::A { name, .. }
+            | DnsRecord::AAAA { name, .. }
+            | DnsRecord::CNAME { name, .. }
+            | DnsRecord::MX { name, .. }
+            | DnsRecord::NS { name, .. }
+            | DnsRecord::SOA { name, .. }
+            | DnsRecord::TXT { name, .. }
+            | DnsRecord::PTR { name, .. }
+            | DnsRecord::SRV { name, .. }
+            | DnsRecord::CAA { name, .. }
+            | DnsRecord::SVCB { name, .. }
+            | DnsRecord::HTTPS { name, .. }
+            | DnsRecord::TLSA { name, .. }
+            | DnsRecord::SSHFP { name, .. }
+            | DnsRecord::DNAME { name, .. }
+            | DnsRecord::NAPTR { name, .. }
+            | DnsRecord::URI { name, .. }
+            | DnsRecord::Unknown { name, .. } => name,
+        }
+    }
+
+    #[must_use]
+    pub fn class(&self) -> DnsClass {
+        match self {
+            DnsRecord::A { class, .. }
+            | DnsRecord::AAAA { class, .. }
+            | DnsRecord::CNAME { class, .. }
+            | DnsRecord::MX { class, .. }
+            | DnsRecord::NS { class, .. }
+            | DnsRecord::SOA { class, .. }
+            | DnsRecord::TXT { class, .. }
+            | DnsRecord::PTR { class, .. }
+            | DnsRecord::SRV { class, .. }
+            | DnsRecord::CAA { class, .. }
+            | DnsRecord::SVCB { class, .. }
+            | DnsRecord::HTTPS { class, .. }
+            | DnsRecord::TLSA { class, .. }
+            | DnsRecord::SSHFP { class, .. }
+            | DnsRecord::DNAME { class, .. }
+            | DnsRecord::NAPTR { class, .. }
+            | DnsRecord::URI { class, .. }
+            | DnsRecord::Unknown { class, .. } => *class,
+        }
+    }
+
+    /// Returns the number of seconds that resolvers may cache the record.
+    /// For records read from a message, this is the TTL received on the wire.
+    #[must_use]
+    pub fn ttl(&self) -> u32 {
+        match self {
+            DnsRecord::A { ttl, .. }
+            | DnsRecord::AAAA { ttl, .. }
+            | DnsRecord::CNAME { ttl, .. }
+            | DnsRecord::MX { ttl, .. }
+            | DnsRecord::NS { ttl, .. }
+            | DnsRecord::SOA { ttl, .. }
+            | DnsRecord::TXT { ttl, .. }
+            | DnsRecord::PTR { ttl, .. }
+            | DnsRecord::SRV { ttl, .. }
+            | DnsRecord::CAA { ttl, .. }
+            | DnsRecord::SVCB { ttl, .. }
+            | DnsRecord::HTTPS { ttl, .. }
+            | DnsRecord::TLSA { ttl, .. }
+            | DnsRecord::SSHFP { ttl, .. }
+            | DnsRecord::DNAME { ttl, .. }
+            | DnsRecord::NAPTR { ttl, .. }
+            | DnsRecord::URI { ttl, .. }
+            | DnsRecord::Unknown { ttl, .. } => *ttl,
         }
     }
 
     #[must_use]
     pub fn typ(&self) -> DnsType {
         match self {
-            DnsRecord::A(_, _) => DnsType::A,
-            DnsRecord::AAAA(_, _) => DnsType::AAAA,
-            DnsRecord::CNAME(_, _) => DnsType::CNAME,
-            DnsRecord::Unknown(_, typ) => DnsType::Unknown(typ.num()),
+            DnsRecord::A { .. } => DnsType::A,
+            DnsRecord::AAAA { .. } => DnsType::AAAA,
+            DnsRecord::CNAME { .. } => DnsType::CNAME,
+            DnsRecord::MX { .. } => DnsType::MX,
+            DnsRecord::NS { .. } => DnsType::NS,
+            DnsRecord::SOA { .. } => DnsType::SOA,
+            DnsRecord::TXT { .. } => DnsType::TXT,
+            DnsRecord::PTR { .. } => DnsType::PTR,
+            DnsRecord::SRV { .. } => DnsType::SRV,
+            DnsRecord::CAA { .. } => DnsType::CAA,
+            DnsRecord::SVCB { .. } => DnsType::SVCB,
+            DnsRecord::HTTPS { .. } => DnsType::HTTPS,
+            DnsRecord::TLSA { .. } => DnsType::TLSA,
+            DnsRecord::SSHFP { .. } => DnsType::SSHFP,
+            DnsRecord::DNAME { .. } => DnsType::DNAME,
+            DnsRecord::NAPTR { .. } => DnsType::NAPTR,
+            DnsRecord::URI { .. } => DnsType::URI,
+            DnsRecord::Unknown { typ, .. } => DnsType::Unknown(typ.num()),
         }
     }
 
+    /// Reads a `<character-string>`.
+    ///
+    /// # Errors
+    /// Returns an error when `buf` does not contain a valid string.
+    fn read_character_string<const N: usize>(buf: &mut FixedBuf<N>) -> Result<Vec<u8>, DnsError> {
+        let mut string = vec![0_u8; usize::from(read_u8(buf)?)];
+        buf.try_read_exact(&mut string).ok_or(DnsError::Truncated)?;
+        Ok(string)
+    }
+
     /// # Errors
     /// Returns an error when `buf` does not contain a valid resource record.
     pub fn read<const N: usize>(buf: &mut FixedBuf<N>) -> Result<Self, DnsError> {
         let name = DnsName::read(buf)?;
         let typ = DnsType::read(buf)?;
+        // Keep records of every class, so messages round-trip.  This includes the EDNS OPT
+        // pseudo-record, whose CLASS field holds the requestor's UDP payload size.
+        // https://datatracker.ietf.org/doc/html/rfc6891#section-6.1.2
         let class = DnsClass::read(buf)?;
-        if class != DnsClass::Internet && class != DnsClass::Any {
-            return Err(DnsError::InvalidClass);
-        }
-        let _ttl_seconds = read_u32_be(buf)?;
-        let rdata = Self::read_rdata(buf)?;
-        match typ {
+        let ttl = read_u32_be(buf)?;
+        let (rdata, rdata_len) = Self::read_rdata(buf)?;
+        let start_len = rdata.len();
+        let record = match typ {
             DnsType::A => {
                 let octets: [u8; 4] = read_exact(rdata)?;
-                Ok(DnsRecord::A(name, Ipv4Addr::from(octets)))
+                Ok(DnsRecord::A {
+                    name,
+                    class,
+                    ttl,
+                    addr: Ipv4Addr::from(octets),
+                })
             }
             DnsType::AAAA => {
                 let octets: [u8; 16] = read_exact(rdata)?;
-                Ok(DnsRecord::AAAA(name, Ipv6Addr::from(octets)))
+                Ok(DnsRecord::AAAA {
+                    name,
+                    class,
+                    ttl,
+                    addr: Ipv6Addr::from(octets),
+                })
             }
-            DnsType::CNAME => Ok(DnsRecord::CNAME(name, DnsName::read(rdata)?)),
-            DnsType::MX
-            | DnsType::NS
-            | DnsType::PTR
-            | DnsType::SOA
-            | DnsType::TXT
-            | DnsType::ANY
-            | DnsType::Unknown(_) => Ok(DnsRecord::Unknown(name, typ)),
+            DnsType::CNAME => Ok(DnsRecord::CNAME {
+                name,
+                class,
+                ttl,
+                target: DnsName::read(rdata)?,
+            }),
+            DnsType::MX => Ok(DnsRecord::MX {
+                name,
+                class,
+                ttl,
+                preference: read_u16_be(rdata)?,
+                exchange: DnsName::read(rdata)?,
+            }),
+            DnsType::NS => Ok(DnsRecord::NS {
+                name,
+                class,
+                ttl,
+                host: DnsName::read(rdata)?,
+            }),
+            DnsType::SOA => Ok(DnsRecord::SOA {
+                name,
+                class,
+                ttl,
+                mname: DnsName::read(rdata)?,
+                rname: DnsName::read(rdata)?,
+                serial: read_u32_be(rdata)?,
+                refresh: read_u32_be(rdata)?,
+                retry: read_u32_be(rdata)?,
+                expire: read_u32_be(rdata)?,
+                minimum: read_u32_be(rdata)?,
+            }),
+            DnsType::TXT => {
+                let mut strings = Vec::new();
+                let mut consumed = 0;
+                while consumed < rdata_len {
+                    let mut string = vec![0_u8; usize::from(read_u8(rdata)?)];
+                    rdata
+                        .try_read_exact(&mut string)
+                        .ok_or(DnsError::Truncated)?;
+                    consumed += 1 + string.len();
+                    strings.push(string);
+                }
+                if consumed != rdata_len {
+                    return Err(DnsError::Truncated);
+                }
+                Ok(DnsRecord::TXT {
+                    name,
+                    class,
+                    ttl,
+                    strings,
+                })
+            }
+            DnsType::PTR => Ok(DnsRecord::PTR {
+                name,
+                class,
+                ttl,
+                target: DnsName::read(rdata)?,
+            }),
+            DnsType::SRV => Ok(DnsRecord::SRV {
+                name,
+                class,
+                ttl,
+                priority: read_u16_be(rdata)?,
+                weight: read_u16_be(rdata)?,
+                port: read_u16_be(rdata)?,
+                target: DnsName::read(rdata)?,
+            }),
+            DnsType::CAA => {
+                let flags = read_u8(rdata)?;
+                let mut tag = vec![0_u8; usize::from(read_u8(rdata)?)];
+                rdata.try_read_exact(&mut tag).ok_or(DnsError::Truncated)?;
+                let tag = String::from_utf8(tag)
+                    .ok()
+                    .filter(|tag| Self::is_valid_caa_tag(tag.as_bytes()))
+                    .ok_or(DnsError::InvalidRdata)?;
+                let value_len = rdata_len
+                    .checked_sub(2 + tag.len())
+                    .ok_or(DnsError::Truncated)?;
+                let mut value = vec![0_u8; value_len];
+                rdata
+                    .try_read_exact(&mut value)
+                    .ok_or(DnsError::Truncated)?;
+                Ok(DnsRecord::CAA {
+                    name,
+                    class,
+                    ttl,
+                    flags,
+                    tag,
+                    value,
+                })
+            }
+            DnsType::SVCB | DnsType::HTTPS => {
+                let priority = read_u16_be(rdata)?;
+                let target = DnsName::read(rdata)?;
+                let params_len = rdata_len
+                    .checked_sub(start_len - rdata.len())
+                    .ok_or(DnsError::Truncated)?;
+                let params = DnsSvcParam::read_params(rdata, params_len)?;
+                // > In AliasMode, ... recipients MUST ignore any SvcParams that are present.
+                // https://datatracker.ietf.org/doc/html/rfc9460#section-2.4.2
+                if priority != 0 {
+                    DnsSvcParam::validate(&params).map_err(|_| DnsError::InvalidRdata)?;
+                }
+                if typ == DnsType::SVCB {
+                    Ok(DnsRecord::SVCB {
+                        name,
+                        class,
+                        ttl,
+                        priority,
+                        target,
+                        params,
+                    })
+                } else {
+                    Ok(DnsRecord::HTTPS {
+                        name,
+                        class,
+                        ttl,
+                        priority,
+                        target,
+                        params,
+                    })
+                }
+            }
+            DnsType::TLSA => {
+                let usage = read_u8(rdata)?;
+                let selector = read_u8(rdata)?;
+                let matching_type = read_u8(rdata)?;
+                let data_len = rdata_len.checked_sub(3).ok_or(DnsError::Truncated)?;
+                let mut data = vec![0_u8; data_len];
+                rdata.try_read_exact(&mut data).ok_or(DnsError::Truncated)?;
+                Ok(DnsRecord::TLSA {
+                    name,
+                    class,
+                    ttl,
+                    usage,
+                    selector,
+                    matching_type,
+                    data,
+                })
+            }
+            DnsType::SSHFP => {
+                let algorithm = read_u8(rdata)?;
+                let fingerprint_type = read_u8(rdata)?;
+                let fingerprint_len = rdata_len.checked_sub(2).ok_or(DnsError::Truncated)?;
+                let mut fingerprint = vec![0_u8; fingerprint_len];
+                rdata
+                    .try_read_exact(&mut fingerprint)
+                    .ok_or(DnsError::Truncated)?;
+                Ok(DnsRecord::SSHFP {
+                    name,
+                    class,
+                    ttl,
+                    algorithm,
+                    fingerprint_type,
+                    fingerprint,
+                })
+            }
+            DnsType::DNAME => Ok(DnsRecord::DNAME {
+                name,
+                class,
+                ttl,
+                target: DnsName::read(rdata)?,
+            }),
+            DnsType::NAPTR => Ok(DnsRecord::NAPTR {
+                name,
+                class,
+                ttl,
+                order: read_u16_be(rdata)?,
+                preference: read_u16_be(rdata)?,
+                flags: Self::read_character_string(rdata)?,
+                service: Self::read_character_string(rdata)?,
+                regexp: Self::read_character_string(rdata)?,
+                replacement: DnsName::read(rdata)?,
+            }),
+            DnsType::URI => {
+                let priority = read_u16_be(rdata)?;
+                let weight = read_u16_be(rdata)?;
+                // The target must not be empty.
+                // https://datatracker.ietf.org/doc/html/rfc7553#section-4
+                let target_len = rdata_len
+                    .checked_sub(4)
+                    .filter(|len| *len > 0)
+                    .ok_or(DnsError::InvalidRdata)?;
+                let mut target = vec![0_u8; target_len];
+                rdata
+                    .try_read_exact(&mut target)
+                    .ok_or(DnsError::Truncated)?;
+                Ok(DnsRecord::URI {
+                    name,
+                    class,
+                    ttl,
+                    priority,
+                    weight,
+                    target: String::from_utf8(target).map_err(|_| DnsError::InvalidRdata)?,
+                })
+            }
+            DnsType::ANY | DnsType::Unknown(_) => {
+                let mut bytes = vec![0_u8; rdata_len];
+                rdata
+                    .try_read_exact(&mut bytes)
+                    .ok_or(DnsError::Truncated)?;
+                Ok(DnsRecord::Unknown {
+                    name,
+                    class,
+                    ttl,
+                    typ,
+                    rdata: bytes,
+                })
+            }
+        }?;
+        // Every type must consume exactly RDLENGTH bytes, or the next record is misread.
+        let consumed = start_len - buf.len();
+        if consumed > rdata_len {
+            return Err(DnsError::Truncated);
+        }
+        if consumed < rdata_len {
+            return Err(DnsError::InvalidRdata);
         }
+        Ok(record)
     }
 
     /// # Errors
     /// Returns an error when `buf` is full.
     pub fn write<const N: usize>(&self, out: &mut FixedBuf<N>) -> Result<(), DnsError> {
-        self.name().write(out)?;
+        self.write_compressed(out, &mut DnsNameCompressor::disabled())
+    }
+
+    /// # Errors
+    /// Returns an error when `buf` is full.
+    pub(crate) fn write_compressed<const N: usize>(
+        &self,
+        out: &mut FixedBuf<N>,
+        compressor: &mut DnsNameCompressor,
+    ) -> Result<(), DnsError> {
+        self.name().write_compressed(out, compressor)?;
         self.typ().write(out)?;
-        DnsClass::Internet.write(out)?;
-        write_u32_be(out, 300)?; // TTL in seconds.
+        self.class().write(out)?;
+        write_u32_be(out, self.ttl())?;
         match self {
-            DnsRecord::A(_, ipv4_addr) => Self::write_rdata(&ipv4_addr.octets(), out),
-            DnsRecord::AAAA(_, ipv6_addr) => Self::write_rdata(&ipv6_addr.octets(), out),
-            DnsRecord::CNAME(_, target_name) => {
-                Self::write_rdata(target_name.as_bytes()?.readable(), out)
+            DnsRecord::A { addr, .. } => Self::write_rdata(&addr.octets(), out),
+            DnsRecord::AAAA { addr, .. } => Self::write_rdata(&addr.octets(), out),
+            DnsRecord::CNAME { target, .. } => compressor.write_rdata(out, |rdata, compressor| {
+                target.write_compressed(rdata, compressor)
+            }),
+            DnsRecord::MX {
+                preference,
+                exchange,
+                ..
+            } => compressor.write_rdata(out, |rdata, compressor| {
+                write_u16_be(rdata, *preference)?;
+                exchange.write_compressed(rdata, compressor)
+            }),
+            DnsRecord::NS { host, .. } => compressor.write_rdata(out, |rdata, compressor| {
+                host.write_compressed(rdata, compressor)
+            }),
+            DnsRecord::SOA {
+                mname,
+                rname,
+                serial,
+                refresh,
+                retry,
+                expire,
+                minimum,
+                ..
+            } => compressor.write_rdata(out, |rdata, compressor| {
+                mname.write_compressed(rdata, compressor)?;
+                rname.write_compressed(rdata, compressor)?;
+                for value in [serial, refresh, retry, expire, minimum] {
+                    write_u32_be(rdata, *value)?;
+                }
+                Ok(())
+            }),
+            DnsRecord::PTR { target, .. } => compressor.write_rdata(out, |rdata, compressor| {
+                target.write_compressed(rdata, compressor)
+            }),
+            // > Unless and until permitted by future standards action, name compression is not to
+            // > be used for this field.
+            // https://datatracker.ietf.org/doc/html/rfc2782
+            DnsRecord::SRV {
+                priority,
+                weight,
+                port,
+                target,
+                ..
+            } => compressor.write_rdata(out, |rdata, _compressor| {
+                write_u16_be(rdata, *priority)?;
+                write_u16_be(rdata, *weight)?;
+                write_u16_be(rdata, *port)?;
+                target.write(rdata)
+            }),
+            DnsRecord::CAA {
+                flags, tag, value, ..
+            } => {
+                let tag_len = u8::try_from(tag.len())
+                    .map_err(|_| DnsError::Internal(format!("CAA tag is too long: {self:?}")))?;
+                let mut rdata: Vec<u8> = vec![*flags, tag_len];
+                rdata.extend_from_slice(tag.as_bytes());
+                rdata.extend_from_slice(value);
+                Self::write_rdata(&rdata, out)
+            }
+            // > TargetName:  The domain name of either the alias target (for AliasMode) or the
+            // > alternative endpoint (for ServiceMode).  ... MUST NOT be compressed.
+            // https://datatracker.ietf.org/doc/html/rfc9460#section-2.2
+            DnsRecord::SVCB {
+                priority,
+                target,
+                params,
+                ..
+            }
+            | DnsRecord::HTTPS {
+                priority,
+                target,
+                params,
+                ..
+            } => {
+                if *priority != 0 {
+                    DnsSvcParam::validate(params).map_err(|e| {
+                        DnsError::Internal(format!("cannot write record {self:?}: {e}"))
+                    })?;
+                }
+                let mut rdata: Vec<u8> = priority.to_be_bytes().to_vec();
+                rdata.extend_from_slice(target.as_bytes()?.readable());
+                DnsSvcParam::write_params(params, &mut rdata)?;
+                Self::write_rdata(&rdata, out)
+            }
+            DnsRecord::TXT { strings, .. } => {
+                let mut rdata: Vec<u8> = Vec::new();
+                for string in strings {
+                    let len = u8::try_from(string.len()).map_err(|_| {
+                        DnsError::Internal(format!("TXT string is too long: {self:?}"))
+                    })?;
+                    rdata.push(len);
+                    rdata.extend_from_slice(string);
+                }
+                Self::write_rdata(&rdata, out)
+            }
+            DnsRecord::TLSA {
+                usage,
+                selector,
+                matching_type,
+                data,
+                ..
+            } => {
+                let mut rdata: Vec<u8> = vec![*usage, *selector, *matching_type];
+                rdata.extend_from_slice(data);
+                Self::write_rdata(&rdata, out)
+            }
+            DnsRecord::SSHFP {
+                algorithm,
+                fingerprint_type,
+                fingerprint,
+                ..
+            } => {
+                let mut rdata: Vec<u8> = vec![*algorithm, *fingerprint_type];
+                rdata.extend_from_slice(fingerprint);
+                Self::write_rdata(&rdata, out)
+            }
+            DnsRecord::DNAME { target, .. } => {
+                Self::write_rdata(target.as_bytes()?.readable(), out)
             }
-            DnsRecord::Unknown(_, _) => {
-                Err(DnsError::Internal(format!("cannot write record {self:?}")))
+            // The replacement is not compressed.
+            // https://datatracker.ietf.org/doc/html/rfc3597#section-4
+            DnsRecord::NAPTR {
+                order,
+                preference,
+                flags,
+                service,
+                regexp,
+                replacement,
+                ..
+            } => {
+                let mut rdata: Vec<u8> = Vec::new();
+                rdata.extend_from_slice(&order.to_be_bytes());
+                rdata.extend_from_slice(&preference.to_be_bytes());
+                for string in [flags, service, regexp] {
+                    let len = u8::try_from(string.len()).map_err(|_| {
+                        DnsError::Internal(format!("NAPTR string is too long: {self:?}"))
+                    })?;
+                    rdata.push(len);
+                    rdata.extend_from_slice(string);
+                }
+                rdata.extend_from_slice(replacement.as_bytes()?.readable());
+                Self::write_rdata(&rdata, out)
             }
+            DnsRecord::URI {
+                priority,
+                weight,
+                target,
+                ..
+            } => {
+                let mut rdata: Vec<u8> = Vec::new();
+                rdata.extend_from_slice(&priority.to_be_bytes());
+                rdata.extend_from_slice(&weight.to_be_bytes());
+                rdata.extend_from_slice(target.as_bytes());
+                Self::write_rdata(&rdata, out)
+            }
+            DnsRecord::Unknown { rdata, .. } => Self::write_rdata(rdata, out),
         }
     }
 }
+
 impl Debug for DnsRecord {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
         match self {
-            DnsRecord::A(name, addr) => write!(f, "DnsRecord::A({name},{addr})"),
-            DnsRecord::AAAA(name, addr) => write!(f, "DnsRecord::AAAA({name},{addr})"),
-            DnsRecord::CNAME(name, target) => write!(f, "DnsRecord::CNAME({name},{target})"),
-            DnsRecord::Unknown(name, typ) => write!(f, "DnsRecord::Unknown({name},{typ})"),
+            DnsRecord::A { name, addr, .. } => write!(f, "DnsRecord::A({name},{addr})"),
+            DnsRecord::AAAA { name, addr, .. } => write!(f, "DnsRecord::AAAA({name},{addr})"),
+            DnsRecord::CNAME { name, target, .. } => {
+                write!(f, "DnsRecord::CNAME({name},{target})")
+            }
+            DnsRecord::MX {
+                name,
+                preference,
+                exchange,
+                ..
+            } => write!(f, "DnsRecord::MX({name},{preference},{exchange})"),
+            DnsRecord::NS { name, host, .. } => write!(f, "DnsRecord::NS({name},{host})"),
+            DnsRecord::SOA {
+                name,
+                mname,
+                rname,
+                serial,
+                refresh,
+                retry,
+                expire,
+                minimum,
+                ..
+            } => write!(
+                f,
+                "DnsRecord::SOA({name},{mname},{rname},{serial},{refresh},{retry},{expire},{minimum})"
+            ),
+            DnsRecord::TXT { name, strings, .. } => {
+                write!(f, "DnsRecord::TXT({name}")?;
+                for string in strings {
+                    write!(f, ",")?;
+                    write_character_string(f, string)?;
+                }
+                write!(f, ")")
+            }
+            DnsRecord::PTR { name, target, .. } => {
+                write!(f, "DnsRecord::PTR({name},{target})")
+            }
+            DnsRecord::SRV {
+                name,
+                priority,
+                weight,
+                port,
+                target,
+                ..
+            } => write!(
+                f,
+                "DnsRecord::SRV({name},{priority},{weight},{port},{target})"
+            ),
+            DnsRecord::CAA {
+                name,
+                flags,
+                tag,
+                value,
+                ..
+            } => {
+                write!(f, "DnsRecord::CAA({name},{flags},{tag},")?;
+                write_character_string(f, value)?;
+                write!(f, ")")
+            }
+            DnsRecord::SVCB {
+                name,
+                priority,
+                target,
+                params,
+                ..
+            }
+            | DnsRecord::HTTPS {
+                name,
+                priority,
+                target,
+                params,
+                ..
+            } => {
+                write!(f, "DnsRecord::{}({name},{priority},{target}", self.typ())?;
+                for param in params {
+                    write!(f, ",{param}")?;
+                }
+                write!(f, ")")
+            }
+            DnsRecord::TLSA {
+                name,
+                usage,
+                selector,
+                matching_type,
+                data,
+                ..
+            } => write!(
+                f,
+                "DnsRecord::TLSA({name},{usage},{selector},{matching_type},{})",
+                hex_encode(data)
+            ),
+            DnsRecord::SSHFP {
+                name,
+                algorithm,
+                fingerprint_type,
+                fingerprint,
+                ..
+            } => write!(
+                f,
+                "DnsRecord::SSHFP({name},{algorithm},{fingerprint_type},{})",
+                hex_encode(fingerprint)
+            ),
+            DnsRecord::DNAME { name, target, .. } => {
+                write!(f, "DnsRecord::DNAME({name},{target})")
+            }
+            DnsRecord::NAPTR {
+                name,
+                order,
+                preference,
+                flags,
+                service,
+                regexp,
+                replacement,
+                ..
+            } => {
+                write!(f, "DnsRecord::NAPTR({name},{order},{preference},")?;
+                for string in [flags, service, regexp] {
+                    write_character_string(f, string)?;
+                    write!(f, ",")?;
+                }
+                write!(f, "{replacement})")
+            }
+            DnsRecord::URI {
+                name,
+                priority,
+                weight,
+                target,
+                ..
+            } => {
+                write!(f, "DnsRecord::URI({name},{priority},{weight},")?;
+                write_character_string(f, target.as_bytes())?;
+                write!(f, ")")
+            }
+            DnsRecord::Unknown { name, rdata, .. } => write!(
+                f,
+                "DnsRecord::Unknown({name},{},\\# {} {})",
+                self.typ(),
+                rdata.len(),
+                hex_encode(rdata)
+            ),
         }
     }
 }
@@ -206,46 +2138,1053 @@ impl Debug for DnsRecord {
 #[cfg(test)]
 #[test]
 fn test_dns_record() {
-    use std::net::{Ipv4Addr, Ipv6Addr};
+    let a_b = || DnsName::new("a.b").unwrap();
     // Constructors
     assert_eq!(
-        DnsRecord::A(DnsName::new("a.b").unwrap(), Ipv4Addr::new(1, 2, 3, 4)),
+        DnsRecord::A {
+            name: a_b(),
+            class: DnsClass::Internet,
+            ttl: 300,
+            addr: Ipv4Addr::new(1, 2, 3, 4)
+        },
         DnsRecord::new_a("a.b", "1.2.3.4").unwrap()
     );
     assert_eq!(
-        DnsRecord::AAAA(
-            DnsName::new("a.b").unwrap(),
-            Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0)
-        ),
+        DnsRecord::AAAA {
+            name: a_b(),
+            class: DnsClass::Internet,
+            ttl: 300,
+            addr: Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0)
+        },
         DnsRecord::new_aaaa("a.b", "2001:db8::").unwrap()
     );
     assert_eq!(
-        DnsRecord::CNAME(DnsName::new("a.b").unwrap(), DnsName::new("c.d").unwrap()),
+        DnsRecord::CNAME {
+            name: a_b(),
+            class: DnsClass::Internet,
+            ttl: 300,
+            target: DnsName::new("c.d").unwrap()
+        },
         DnsRecord::new_cname("a.b", "c.d").unwrap()
     );
+    DnsRecord::new_a("_acme-challenge.b", "1.2.3.4").unwrap_err();
+    DnsRecord::new_aaaa("_acme-challenge.b", "2001:db8::").unwrap_err();
+    DnsRecord::new_cname("_acme-challenge.b", "c.d").unwrap();
     // Debug
     assert_eq!(
         "DnsRecord::A(a.b,1.2.3.4)",
-        format!(
-            "{:?}",
-            DnsRecord::A(DnsName::new("a.b").unwrap(), Ipv4Addr::new(1, 2, 3, 4))
-        )
+        format!("{:?}", DnsRecord::new_a("a.b", "1.2.3.4").unwrap())
     );
     assert_eq!(
         "DnsRecord::AAAA(a.b,2001:db8::)",
-        format!(
-            "{:?}",
-            DnsRecord::AAAA(
-                DnsName::new("a.b").unwrap(),
-                Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0)
-            )
-        )
+        format!("{:?}", DnsRecord::new_aaaa("a.b", "2001:db8::").unwrap())
     );
     assert_eq!(
         "DnsRecord::CNAME(a.b,c.d)",
+        format!("{:?}", DnsRecord::new_cname("a.b", "c.d").unwrap())
+    );
+}
+
+#[cfg(test)]
+#[test]
+fn test_ttl_and_class() {
+    let record = DnsRecord::new_a("a.b", "1.2.3.4").unwrap();
+    assert_eq!(DnsRecord::DEFAULT_TTL, record.ttl());
+    assert_eq!(DnsClass::Internet, record.class());
+    let record = record.with_ttl(86400).with_class(DnsClass::Any);
+    assert_eq!(86400, record.ttl());
+    assert_eq!(DnsClass::Any, record.class());
+    let mut out: FixedBuf<64> = FixedBuf::new();
+    record.write(&mut out).unwrap();
+    assert_eq!(
+        b"\x01a\x01b\x00\x00\x01\x00\xFF\x00\x01\x51\x80\x00\x04\x01\x02\x03\x04",
+        out.readable()
+    );
+    assert_eq!(record, DnsRecord::read(&mut out).unwrap());
+    // Keeps the TTL received on the wire.
+    let mut buf: FixedBuf<64> = FixedBuf::new();
+    buf.write_bytes(b"\x01a\x00\x00\x05\x00\x01\x00\x00\x00\x3C\x00\x03\x01c\x00")
+        .unwrap();
+    let record = DnsRecord::read(&mut buf).unwrap();
+    assert_eq!(60, record.ttl());
+    assert_eq!(DnsRecord::new_cname("a", "c").unwrap().with_ttl(60), record);
+}
+
+#[cfg(test)]
+#[test]
+fn test_mx() {
+    assert_eq!(
+        DnsRecord::MX {
+            name: DnsName::new("example.com").unwrap(),
+            class: DnsClass::Internet,
+            ttl: 300,
+            preference: 10,
+            exchange: DnsName::new("mail.example.com").unwrap()
+        },
+        DnsRecord::new_mx("example.com", 10, "mail.example.com").unwrap()
+    );
+    DnsRecord::new_mx("example.com", 10, "_mail.example.com").unwrap_err();
+    DnsRecord::new_mx("example.com", 10, "").unwrap_err();
+    let record = DnsRecord::new_mx("a.b", 258, "c.a.b").unwrap();
+    assert_eq!("DnsRecord::MX(a.b,258,c.a.b)", format!("{record:?}"));
+    assert_eq!(DnsType::MX, record.typ());
+    let mut out: FixedBuf<64> = FixedBuf::new();
+    record.write(&mut out).unwrap();
+    assert_eq!(
+        b"\x01a\x01b\x00\x00\x0F\x00\x01\x00\x00\x01\x2C\x00\x09\x01\x02\x01c\x01a\x01b\x00",
+        out.readable()
+    );
+    assert_eq!(record, DnsRecord::read(&mut out).unwrap());
+    // Compressed exchange.
+    let mut out: FixedBuf<64> = FixedBuf::new();
+    let mut compressor = DnsNameCompressor::new(&mut out);
+    record.write_compressed(&mut out, &mut compressor).unwrap();
+    assert_eq!(
+        b"\x01a\x01b\x00\x00\x0F\x00\x01\x00\x00\x01\x2C\x00\x06\x01\x02\x01c\xC0\x00",
+        out.readable()
+    );
+    assert_eq!(record, DnsRecord::read(&mut out).unwrap());
+    check_rdlength(&record);
+}
+
+#[cfg(test)]
+#[test]
+fn test_ns() {
+    assert_eq!(
+        DnsRecord::NS {
+            name: DnsName::new("sub.example.com").unwrap(),
+            class: DnsClass::Internet,
+            ttl: 300,
+            host: DnsName::new("ns1.sub.example.com").unwrap()
+        },
+        DnsRecord::new_ns("sub.example.com", "ns1.sub.example.com").unwrap()
+    );
+    DnsRecord::new_ns("sub.example.com", "_ns1.sub.example.com").unwrap_err();
+    let record = DnsRecord::new_ns("a.b", "c.a.b").unwrap();
+    assert_eq!("DnsRecord::NS(a.b,c.a.b)", format!("{record:?}"));
+    assert_eq!(DnsType::NS, record.typ());
+    let mut out: FixedBuf<64> = FixedBuf::new();
+    let mut compressor = DnsNameCompressor::new(&mut out);
+    record.write_compressed(&mut out, &mut compressor).unwrap();
+    assert_eq!(
+        b"\x01a\x01b\x00\x00\x02\x00\x01\x00\x00\x01\x2C\x00\x04\x01c\xC0\x00",
+        out.readable()
+    );
+    assert_eq!(record, DnsRecord::read(&mut out).unwrap());
+    check_rdlength(&record);
+}
+
+#[cfg(test)]
+#[test]
+fn test_soa() {
+    let record = DnsRecord::new_soa("a.b", "ns.a.b", "hostmaster@a.b", 2024010101).unwrap();
+    assert_eq!(
+        DnsRecord::SOA {
+            name: DnsName::new("a.b").unwrap(),
+            class: DnsClass::Internet,
+            ttl: 300,
+            mname: DnsName::new("ns.a.b").unwrap(),
+            rname: DnsName::new("hostmaster.a.b").unwrap(),
+            serial: 2024010101,
+            refresh: 7200,
+            retry: 3600,
+            expire: 1209600,
+            minimum: 3600
+        },
+        record
+    );
+    assert_eq!(
+        record,
+        DnsRecord::new_soa("a.b", "ns.a.b", "hostmaster.a.b", 2024010101).unwrap()
+    );
+    assert_eq!(
+        "DnsRecord::SOA(a.b,ns.a.b,john\\.doe.a.b,1,7200,3600,1209600,3600)",
         format!(
             "{:?}",
-            DnsRecord::CNAME(DnsName::new("a.b").unwrap(), DnsName::new("c.d").unwrap())
+            DnsRecord::new_soa("a.b", "ns.a.b", "john.doe@a.b", 1).unwrap()
         )
     );
+    DnsRecord::new_soa("a.b", "_ns.a.b", "hostmaster@a.b", 1).unwrap_err();
+    DnsRecord::new_soa("a.b", "ns.a.b", "@a.b", 1).unwrap_err();
+    assert_eq!(DnsType::SOA, record.typ());
+    let mut out: FixedBuf<128> = FixedBuf::new();
+    let mut compressor = DnsNameCompressor::new(&mut out);
+    record.write_compressed(&mut out, &mut compressor).unwrap();
+    assert_eq!(
+        &b"\x01a\x01b\x00\x00\x06\x00\x01\x00\x00\x01\x2C\x00\x26\
+            \x02ns\xC0\x00\x0Ahostmaster\xC0\x00\
+            \x78\xA3\xF1\x75\x00\x00\x1C\x20\x00\x00\x0E\x10\x00\x12\x75\x00\x00\x00\x0E\x10"[..],
+        out.readable()
+    );
+    assert_eq!(record, DnsRecord::read(&mut out).unwrap());
+    check_rdlength(&record);
+}
+
+#[cfg(test)]
+#[test]
+fn test_txt() {
+    assert_eq!(
+        DnsRecord::TXT {
+            name: DnsName::new("_acme-challenge.example.com").unwrap(),
+            class: DnsClass::Internet,
+            ttl: 300,
+            strings: vec![b"token".to_vec()]
+        },
+        DnsRecord::new_txt("_acme-challenge.example.com", "token").unwrap()
+    );
+    assert_eq!(
+        DnsRecord::new_txt("a.b", "").unwrap(),
+        DnsRecord::new_txt_bytes("a.b", b"").unwrap()
+    );
+    let record = DnsRecord::new_txt_bytes("a.b", b"").unwrap();
+    assert_eq!("DnsRecord::TXT(a.b,\"\")", format!("{record:?}"));
+    // Splits long values.
+    let dkim = "v=DKIM1; k=rsa; p=".to_string() + &"A".repeat(392);
+    let record = DnsRecord::new_txt("s1._domainkey.example.com", &dkim).unwrap();
+    match &record {
+        DnsRecord::TXT { strings, .. } => {
+            assert_eq!(
+                vec![255, 155],
+                strings.iter().map(Vec::len).collect::<Vec<_>>()
+            );
+            assert_eq!(dkim.as_bytes(), strings.concat());
+        }
+        _ => unreachable!(),
+    }
+    let mut out: FixedBuf<512> = FixedBuf::new();
+    record.write(&mut out).unwrap();
+    assert_eq!(record, DnsRecord::read(&mut out).unwrap());
+    // Wire format.
+    let record = DnsRecord::TXT {
+        name: DnsName::new("a.b").unwrap(),
+        class: DnsClass::Internet,
+        ttl: 300,
+        strings: vec![b"x\"y".to_vec(), b"".to_vec(), b"\x00".to_vec()],
+    };
+    assert_eq!(
+        "DnsRecord::TXT(a.b,\"x\\\"y\",\"\",\"\\000\")",
+        format!("{record:?}")
+    );
+    let mut out: FixedBuf<64> = FixedBuf::new();
+    record.write(&mut out).unwrap();
+    assert_eq!(
+        b"\x01a\x01b\x00\x00\x10\x00\x01\x00\x00\x01\x2C\x00\x07\x03x\"y\x00\x01\x00",
+        out.readable()
+    );
+    assert_eq!(record, DnsRecord::read(&mut out).unwrap());
+    // A string longer than RDLENGTH.
+    let mut buf: FixedBuf<64> = FixedBuf::new();
+    buf.write_bytes(b"\x01a\x00\x00\x10\x00\x01\x00\x00\x01\x2C\x00\x02\x02ab")
+        .unwrap();
+    assert_eq!(Err(DnsError::Truncated), DnsRecord::read(&mut buf));
+    // Strings longer than 255 bytes.
+    let record = DnsRecord::TXT {
+        name: DnsName::new("a.b").unwrap(),
+        class: DnsClass::Internet,
+        ttl: 300,
+        strings: vec![vec![b'a'; 256]],
+    };
+    let mut out: FixedBuf<512> = FixedBuf::new();
+    assert!(matches!(record.write(&mut out), Err(DnsError::Internal(_))));
+}
+
+#[cfg(test)]
+#[test]
+fn test_ptr() {
+    let expected = DnsRecord::PTR {
+        name: DnsName::new("4.3.2.1.in-addr.arpa").unwrap(),
+        class: DnsClass::Internet,
+        ttl: 300,
+        target: DnsName::new("device-1.example.com").unwrap(),
+    };
+    assert_eq!(
+        expected,
+        DnsRecord::new_ptr("4.3.2.1.in-addr.arpa", "device-1.example.com").unwrap()
+    );
+    assert_eq!(
+        expected,
+        DnsRecord::new_ptr("1.2.3.4", "device-1.example.com").unwrap()
+    );
+    assert_eq!(
+        expected,
+        DnsRecord::new_ptr_for_ip("1.2.3.4".parse().unwrap(), "device-1.example.com").unwrap()
+    );
+    assert_eq!(
+        DnsName::from_ip("2001:db8::1".parse().unwrap()),
+        *DnsRecord::new_ptr("2001:db8::1", "device-1.example.com")
+            .unwrap()
+            .name()
+    );
+    // RFC 2317 classless delegation.
+    DnsRecord::new_ptr("65.64/26.2.0.192.in-addr.arpa", "device-1.example.com").unwrap();
+    DnsRecord::new_ptr("1.2.3.4", "_device.example.com").unwrap_err();
+    DnsRecord::new_ptr("1..2", "device-1.example.com").unwrap_err();
+    DnsRecord::new_ptr("a b!", "device-1.example.com").unwrap_err();
+    DnsRecord::new_ptr("a b!.64/26.2.0.192.in-addr.arpa", "device-1.example.com").unwrap_err();
+    DnsRecord::new_ptr("1.64/26.example.com", "device-1.example.com").unwrap_err();
+    DnsRecord::new_ptr("host.example.com", "device-1.example.com").unwrap();
+    let record = DnsRecord::new_ptr("1.0.0.10.in-addr.arpa", "a.in-addr.arpa").unwrap();
+    assert_eq!(
+        "DnsRecord::PTR(1.0.0.10.in-addr.arpa,a.in-addr.arpa)",
+        format!("{record:?}")
+    );
+    assert_eq!(DnsType::PTR, record.typ());
+    let mut out: FixedBuf<64> = FixedBuf::new();
+    let mut compressor = DnsNameCompressor::new(&mut out);
+    record.write_compressed(&mut out, &mut compressor).unwrap();
+    assert_eq!(
+        &b"\x011\x010\x010\x0210\x07in-addr\x04arpa\x00\x00\x0C\x00\x01\x00\x00\x01\x2C\
+            \x00\x04\x01a\xC0\x09"[..],
+        out.readable()
+    );
+    assert_eq!(record, DnsRecord::read(&mut out).unwrap());
+    check_rdlength(&record);
+}
+
+#[cfg(test)]
+#[test]
+fn test_srv() {
+    assert_eq!(
+        DnsRecord::SRV {
+            name: DnsName::new("_mqtt._tcp.example.com").unwrap(),
+            class: DnsClass::Internet,
+            ttl: 300,
+            priority: 10,
+            weight: 5,
+            port: 8883,
+            target: DnsName::new("broker.example.com").unwrap()
+        },
+        DnsRecord::new_srv("_mqtt._tcp.example.com", 10, 5, 8883, "broker.example.com").unwrap()
+    );
+    let record = DnsRecord::new_srv("_mqtt._tcp.example.com", 0, 0, 0, ".").unwrap();
+    assert_eq!(
+        "DnsRecord::SRV(_mqtt._tcp.example.com,0,0,0,.)",
+        format!("{record:?}")
+    );
+    DnsRecord::new_srv("_mqtt._tcp.example.com", 0, 0, 1, "_broker.example.com").unwrap_err();
+    let record = DnsRecord::new_srv("_a._tcp.b", 1, 2, 258, "c.b").unwrap();
+    assert_eq!(DnsType::SRV, record.typ());
+    // The target is not compressed.
+    let mut out: FixedBuf<64> = FixedBuf::new();
+    let mut compressor = DnsNameCompressor::new(&mut out);
+    record.write_compressed(&mut out, &mut compressor).unwrap();
+    assert_eq!(
+        &b"\x02_a\x04_tcp\x01b\x00\x00\x21\x00\x01\x00\x00\x01\x2C\x00\x0B\
+            \x00\x01\x00\x02\x01\x02\x01c\x01b\x00"[..],
+        out.readable()
+    );
+    assert_eq!(record, DnsRecord::read(&mut out).unwrap());
+    check_rdlength(&record);
+}
+
+#[cfg(test)]
+#[test]
+fn test_caa() {
+    let expected = DnsRecord::CAA {
+        name: DnsName::new("example.com").unwrap(),
+        class: DnsClass::Internet,
+        ttl: 300,
+        flags: 0,
+        tag: "issue".to_string(),
+        value: b"letsencrypt.org".to_vec(),
+    };
+    assert_eq!(
+        expected,
+        DnsRecord::new_caa("example.com", 0, "issue", "letsencrypt.org").unwrap()
+    );
+    assert_eq!(
+        expected,
+        DnsRecord::parse_caa("example.com", "0 issue \"letsencrypt.org\"").unwrap()
+    );
+    assert_eq!(
+        expected,
+        DnsRecord::parse_caa("example.com", " 0  issue  letsencrypt.org ").unwrap()
+    );
+    assert_eq!(
+        "DnsRecord::CAA(example.com,0,issue,\"letsencrypt.org\")",
+        format!("{expected:?}")
+    );
+    DnsRecord::parse_caa("example.com", "128 issue \";\"").unwrap();
+    DnsRecord::parse_caa("example.com", "0 iodef \"mailto:security@example.com\"").unwrap();
+    DnsRecord::parse_caa("example.com", "0 issue \"ca.example.net; account=230123\"").unwrap();
+    DnsRecord::parse_caa("example.com", "0 tbs \"Unknown\"").unwrap();
+    DnsRecord::parse_caa("example.com", "0 issue \"\"").unwrap();
+    // Reserved flag bits.
+    DnsRecord::new_caa("example.com", 1, "issue", "ca.example").unwrap_err();
+    DnsRecord::parse_caa("example.com", "129 issue \"ca.example\"").unwrap_err();
+    // Tags.
+    DnsRecord::new_caa("example.com", 0, "", "ca.example").unwrap_err();
+    DnsRecord::new_caa("example.com", 0, "is-sue", "ca.example").unwrap_err();
+    DnsRecord::new_caa("example.com", 0, "issue_wild", "ca.example").unwrap_err();
+    DnsRecord::new_caa("example.com", 0, "a123456789abcdef", "ca.example").unwrap_err();
+    DnsRecord::new_caa("example.com", 0, "a123456789abcde", "ca.example").unwrap();
+    // Presentation format.
+    for rdata in [
+        "",
+        "0 issue",
+        "0 issue ca.example extra",
+        "256 issue ca.example",
+        "x issue ca.example",
+        "0 issue \"ca.example",
+    ] {
+        DnsRecord::parse_caa("example.com", rdata).unwrap_err();
+    }
+    // Wire format.
+    let record = DnsRecord::new_caa("a.b", 128, "issue", "c;").unwrap();
+    assert_eq!(DnsType::CAA, record.typ());
+    let mut out: FixedBuf<64> = FixedBuf::new();
+    record.write(&mut out).unwrap();
+    assert_eq!(
+        b"\x01a\x01b\x00\x01\x01\x00\x01\x00\x00\x01\x2C\x00\x09\x80\x05issuec;",
+        out.readable()
+    );
+    assert_eq!(record, DnsRecord::read(&mut out).unwrap());
+    // Readers ignore reserved flag bits.
+    let mut buf: FixedBuf<64> = FixedBuf::new();
+    buf.write_bytes(b"\x01a\x00\x01\x01\x00\x01\x00\x00\x01\x2C\x00\x05\x01\x03tbs")
+        .unwrap();
+    assert_eq!(
+        DnsRecord::CAA {
+            name: DnsName::new("a").unwrap(),
+            class: DnsClass::Internet,
+            ttl: 300,
+            flags: 1,
+            tag: "tbs".to_string(),
+            value: Vec::new(),
+        },
+        DnsRecord::read(&mut buf).unwrap()
+    );
+    for bytes in [
+        // Empty tag.
+        &b"\x01a\x00\x01\x01\x00\x01\x00\x00\x01\x2C\x00\x03\x00\x00x"[..],
+        // Bad tag character.
+        b"\x01a\x00\x01\x01\x00\x01\x00\x00\x01\x2C\x00\x04\x00\x02a-",
+    ] {
+        let mut buf: FixedBuf<64> = FixedBuf::new();
+        buf.write_bytes(bytes).unwrap();
+        assert_eq!(Err(DnsError::InvalidRdata), DnsRecord::read(&mut buf));
+    }
+    // Tag longer than RDLENGTH.
+    let mut buf: FixedBuf<64> = FixedBuf::new();
+    buf.write_bytes(b"\x01a\x00\x01\x01\x00\x01\x00\x00\x01\x2C\x00\x03\x00\x05issue")
+        .unwrap();
+    assert_eq!(Err(DnsError::Truncated), DnsRecord::read(&mut buf));
+}
+
+#[cfg(test)]
+#[test]
+fn test_svcb() {
+    use std::net::Ipv4Addr;
+    let h3 = || DnsSvcParam::Alpn(vec![b"h3".to_vec(), b"h2".to_vec()]);
+    let record = DnsRecord::new_https(
+        "api.example.com",
+        1,
+        ".",
+        vec![DnsSvcParam::Port(8443), h3()],
+    )
+    .unwrap();
+    assert_eq!(
+        DnsRecord::HTTPS {
+            name: DnsName::new("api.example.com").unwrap(),
+            class: DnsClass::Internet,
+            ttl: 300,
+            priority: 1,
+            target: DnsName::root(),
+            params: vec![h3(), DnsSvcParam::Port(8443)],
+        },
+        record
+    );
+    assert_eq!(DnsType::HTTPS, record.typ());
+    assert_eq!(
+        "DnsRecord::HTTPS(api.example.com,1,.,alpn=h3,h2,port=8443)",
+        format!("{record:?}")
+    );
+    let alias =
+        DnsRecord::new_svcb("_8443._foo.api.example.com", 0, "svc.example.net", vec![]).unwrap();
+    assert_eq!(DnsType::SVCB, alias.typ());
+    assert_eq!(
+        "DnsRecord::SVCB(_8443._foo.api.example.com,0,svc.example.net)",
+        format!("{alias:?}")
+    );
+    DnsRecord::new_https("api.example.com", 0, "svc.example.net", vec![h3()]).unwrap_err();
+    DnsRecord::new_https("api.example.com", 1, ".", vec![DnsSvcParam::NoDefaultAlpn]).unwrap_err();
+    DnsRecord::new_https(
+        "api.example.com",
+        1,
+        ".",
+        vec![DnsSvcParam::Port(1), DnsSvcParam::Port(2)],
+    )
+    .unwrap_err();
+    DnsRecord::new_https("api.example.com", 1, "a..b", vec![]).unwrap_err();
+    // Wire format, with an uncompressed target.
+    let record = DnsRecord::new_svcb(
+        "a.b",
+        16,
+        "c.a.b",
+        vec![
+            DnsSvcParam::Ipv4Hint(vec![Ipv4Addr::new(192, 0, 2, 1)]),
+            DnsSvcParam::Mandatory(vec![DnsSvcParam::IPV4HINT]),
+        ],
+    )
+    .unwrap();
+    let mut out: FixedBuf<128> = FixedBuf::new();
+    let mut compressor = DnsNameCompressor::new(&mut out);
+    record.write_compressed(&mut out, &mut compressor).unwrap();
+    assert_eq!(
+        &b"\x01a\x01b\x00\x00\x40\x00\x01\x00\x00\x01\x2C\x00\x17\x00\x10\x01c\x01a\x01b\x00\
+            \x00\x00\x00\x02\x00\x04\x00\x04\x00\x04\xC0\x00\x02\x01"[..],
+        out.readable()
+    );
+    assert_eq!(record, DnsRecord::read(&mut out).unwrap());
+    let mut out: FixedBuf<128> = FixedBuf::new();
+    alias.write(&mut out).unwrap();
+    assert_eq!(alias, DnsRecord::read(&mut out).unwrap());
+    // Reading rejects params out of order in ServiceMode.
+    let mut buf: FixedBuf<128> = FixedBuf::new();
+    buf.write_bytes(
+        b"\x01a\x00\x00\x41\x00\x01\x00\x00\x01\x2C\x00\x0D\x00\x01\x00\
+        \x00\x03\x00\x02\x01\xBB\x00\x02\x00\x00",
+    )
+    .unwrap();
+    assert_eq!(Err(DnsError::InvalidRdata), DnsRecord::read(&mut buf));
+    // Writing rejects invalid params.
+    let record = DnsRecord::HTTPS {
+        name: DnsName::new("a").unwrap(),
+        class: DnsClass::Internet,
+        ttl: 300,
+        priority: 1,
+        target: DnsName::root(),
+        params: vec![DnsSvcParam::Port(443), h3()],
+    };
+    let mut out: FixedBuf<128> = FixedBuf::new();
+    assert!(matches!(record.write(&mut out), Err(DnsError::Internal(_))));
+}
+
+#[cfg(test)]
+#[test]
+fn test_unknown() {
+    let record = DnsRecord::new_unknown("a.b", "TYPE65280", "\\# 5 0A00 0001 ff").unwrap();
+    assert_eq!(
+        DnsRecord::Unknown {
+            name: DnsName::new("a.b").unwrap(),
+            class: DnsClass::Internet,
+            ttl: 300,
+            typ: DnsType::Unknown(65280),
+            rdata: vec![0x0A, 0x00, 0x00, 0x01, 0xFF],
+        },
+        record
+    );
+    assert_eq!(DnsType::Unknown(65280), record.typ());
+    assert_eq!(
+        "DnsRecord::Unknown(a.b,TYPE65280,\\# 5 0A000001FF)",
+        format!("{record:?}")
+    );
+    assert_eq!(
+        Ok(vec![]),
+        DnsRecord::new_unknown("a.b", "type731", " \\# 0 ").map(|record| match record {
+            DnsRecord::Unknown { rdata, .. } => rdata,
+            _ => unreachable!(),
+        })
+    );
+    for (typ, rdata) in [
+        ("TYPE1", "\\# 4 0A000001"),
+        ("MX", "\\# 0"),
+        ("TYPE", "\\# 0"),
+        ("TYPE65536", "\\# 0"),
+        ("FOO", "\\# 0"),
+        ("TYPE65280", ""),
+        ("TYPE65280", "\\#"),
+        ("TYPE65280", "\\#0"),
+        ("TYPE65280", "# 0"),
+        ("TYPE65280", "0A"),
+        ("TYPE65280", "\\# 2 0A"),
+        ("TYPE65280", "\\# 1 0A0B"),
+        ("TYPE65280", "\\# 1 A"),
+        ("TYPE65280", "\\# 1 0G"),
+        ("TYPE65280", "\\# x 0A"),
+    ] {
+        DnsRecord::new_unknown("a.b", typ, rdata).unwrap_err();
+    }
+    // Round-trips the RDATA, TTL, and class.
+    let mut buf: FixedBuf<64> = FixedBuf::new();
+    let bytes = b"\x01a\x00\xFF\x00\x00\xFF\x00\x00\x0E\x10\x00\x03\x01\x02\x03\x01b\x00";
+    buf.write_bytes(bytes).unwrap();
+    let record = DnsRecord::read(&mut buf).unwrap();
+    assert_eq!(
+        DnsRecord::new_unknown("a", "TYPE65280", "\\# 3 010203")
+            .unwrap()
+            .with_ttl(3600)
+            .with_class(DnsClass::Any),
+        record
+    );
+    let mut out: FixedBuf<64> = FixedBuf::new();
+    record.write(&mut out).unwrap();
+    assert_eq!(&bytes[..16], out.readable());
+    // Reads the next record after the RDATA.
+    assert_eq!(b"\x01b\x00", buf.readable());
+}
+
+#[cfg(test)]
+#[test]
+fn test_tlsa() {
+    let sha256 = b"\xBA\x78\x16\xBF\x8F\x01\xCF\xEA\x41\x41\x40\xDE\x5D\xAE\x22\x23\
+        \xB0\x03\x61\xA3\x96\x17\x7A\x9C\xB4\x10\xFF\x61\xF2\x00\x15\xAD";
+    let record = DnsRecord::new_tlsa(
+        "_25._tcp.mail.example.com",
+        DnsRecord::TLSA_USAGE_DANE_EE,
+        DnsRecord::TLSA_SELECTOR_SPKI,
+        DnsRecord::TLSA_MATCHING_SHA256,
+        sha256,
+    )
+    .unwrap();
+    assert_eq!(
+        DnsRecord::TLSA {
+            name: DnsName::new("_25._tcp.mail.example.com").unwrap(),
+            class: DnsClass::Internet,
+            ttl: 300,
+            usage: 3,
+            selector: 1,
+            matching_type: 1,
+            data: sha256.to_vec(),
+        },
+        record
+    );
+    assert_eq!(DnsType::TLSA, record.typ());
+    assert_eq!(
+        "DnsRecord::TLSA(_25._tcp.mail.example.com,3,1,1,\
+        BA7816BF8F01CFEA414140DE5DAE2223B00361A396177A9CB410FF61F20015AD)",
+        format!("{record:?}")
+    );
+    assert_eq!(
+        Ok(&record),
+        DnsRecord::parse_tlsa(
+            "_25._tcp.mail.example.com",
+            "3 1 1 BA7816BF8F01CFEA414140DE5DAE2223\n b00361a396177a9cb410ff61f20015ad"
+        )
+        .as_ref()
+    );
+    DnsRecord::new_tlsa("a", 3, 1, 1, &sha256[1..]).unwrap_err();
+    DnsRecord::new_tlsa("a", 3, 1, 2, sha256).unwrap_err();
+    DnsRecord::new_tlsa("a", 3, 1, 0, b"").unwrap_err();
+    DnsRecord::new_tlsa("a", 3, 1, 0, b"\x01").unwrap();
+    DnsRecord::new_tlsa("a", 255, 255, 255, b"\x01").unwrap();
+    DnsRecord::parse_tlsa("a", "3 1 1").unwrap_err();
+    DnsRecord::parse_tlsa("a", "3 1 0 0").unwrap_err();
+    DnsRecord::parse_tlsa("a", "3 1 256 00").unwrap_err();
+    // Wire format.
+    let record = DnsRecord::new_tlsa("a.b", 2, 0, 0, b"\x30\x00").unwrap();
+    let mut out: FixedBuf<64> = FixedBuf::new();
+    record.write(&mut out).unwrap();
+    assert_eq!(
+        b"\x01a\x01b\x00\x00\x34\x00\x01\x00\x00\x01\x2C\x00\x05\x02\x00\x00\x30\x00",
+        out.readable()
+    );
+    assert_eq!(record, DnsRecord::read(&mut out).unwrap());
+    let mut buf: FixedBuf<64> = FixedBuf::new();
+    buf.write_bytes(b"\x01a\x00\x00\x34\x00\x01\x00\x00\x01\x2C\x00\x02\x03\x01")
+        .unwrap();
+    assert_eq!(Err(DnsError::Truncated), DnsRecord::read(&mut buf));
+}
+
+#[cfg(all(test, feature = "sha2"))]
+#[test]
+fn test_tlsa_from_der() {
+    // The ISRG Root X2 certificate.  The expected hashes are from
+    // `openssl x509 -pubkey -noout | openssl pkey -pubin -outform DER | sha256sum`
+    // and `openssl x509 -outform DER | sha256sum`.
+    let isrg_root_x2 = crate::dns_presentation::base64_decode(
+        b"MIICGzCCAaGgAwIBAgIQQdKd0XLq7qeAwSxs6S+HUjAKBggqhkjOPQQDAzBPMQsw\
+        CQYDVQQGEwJVUzEpMCcGA1UEChMgSW50ZXJuZXQgU2VjdXJpdHkgUmVzZWFyY2gg\
+        R3JvdXAxFTATBgNVBAMTDElTUkcgUm9vdCBYMjAeFw0yMDA5MDQwMDAwMDBaFw00\
+        MDA5MTcxNjAwMDBaME8xCzAJBgNVBAYTAlVTMSkwJwYDVQQKEyBJbnRlcm5ldCBT\
+        ZWN1cml0eSBSZXNlYXJjaCBHcm91cDEVMBMGA1UEAxMMSVNSRyBSb290IFgyMHYw\
+        EAYHKoZIzj0CAQYFK4EEACIDYgAEzZvVn4CDCuwJSvMWSj5cz3es3mcFDR0HttwW\
+        +1qLFNvicWDEukWVEYmO6gbf9yoWHKS5xcUy4APgHoIYOIvXRdgKam7mAHf7AlF9\
+        ItgKbppbd9/w+kHsOdx1ymgHDB/qo0IwQDAOBgNVHQ8BAf8EBAMCAQYwDwYDVR0T\
+        AQH/BAUwAwEB/zAdBgNVHQ4EFgQUfEKWrt5LSDv6kviejM9ti6lyN5UwCgYIKoZI\
+        zj0EAwMDaAAwZQIwe3lORlCEwkSHRhtFcP9Ymd70/aTSVaYgLXTWNLxBo1BfASdW\
+        tL4ndQavEi51mI38AjEAi/V3bNTIZargCyzuFJ0nN6T5U6VR5CmD1/iQMVtCnwr1\
+        /q4AaOeMSQ+2b1tbFfLn",
+    )
+    .unwrap();
+    let name = "_443._tcp.www.example.com";
+    assert_eq!(
+        DnsRecord::parse_tlsa(
+            name,
+            "2 1 1 762195c225586ee6c0237456e2107dc54f1efc21f61a792ebd515913cce68332"
+        ),
+        DnsRecord::new_tlsa_from_der(name, 2, 1, 1, &isrg_root_x2)
+    );
+    assert_eq!(
+        DnsRecord::parse_tlsa(
+            name,
+            "3 0 1 69729b8e15a86efc177a57afb7171dfc64add28c2fca8cf1507e34453ccb1470"
+        ),
+        DnsRecord::new_tlsa_from_der(name, 3, 0, 1, &isrg_root_x2)
+    );
+    DnsRecord::new_tlsa_from_der(name, 3, 1, 1, &isrg_root_x2[..isrg_root_x2.len() - 1])
+        .unwrap_err();
+    DnsRecord::new_tlsa_from_der(name, 3, 1, 1, b"abc").unwrap_err();
+    assert_eq!(
+        DnsRecord::new_tlsa(
+            name,
+            3,
+            0,
+            1,
+            b"\xBA\x78\x16\xBF\x8F\x01\xCF\xEA\x41\x41\x40\xDE\x5D\xAE\x22\x23\
+            \xB0\x03\x61\xA3\x96\x17\x7A\x9C\xB4\x10\xFF\x61\xF2\x00\x15\xAD"
+        ),
+        DnsRecord::new_tlsa_from_der(name, 3, 0, 1, b"abc")
+    );
+    assert_eq!(
+        DnsRecord::new_tlsa(
+            name,
+            2,
+            0,
+            2,
+            b"\xDD\xAF\x35\xA1\x93\x61\x7A\xBA\xCC\x41\x73\x49\xAE\x20\x41\x31\
+            \x12\xE6\xFA\x4E\x89\xA9\x7E\xA2\x0A\x9E\xEE\xE6\x4B\x55\xD3\x9A\
+            \x21\x92\x99\x2A\x27\x4F\xC1\xA8\x36\xBA\x3C\x23\xA3\xFE\xEB\xBD\
+            \x45\x4D\x44\x23\x64\x3C\xE8\x0E\x2A\x9A\xC9\x4F\xA5\x4C\xA4\x9F"
+        ),
+        DnsRecord::new_tlsa_from_der(name, 2, 0, 2, b"abc")
+    );
+    assert_eq!(
+        DnsRecord::new_tlsa(name, 3, 0, 0, b"abc"),
+        DnsRecord::new_tlsa_from_der(name, 3, 0, 0, b"abc")
+    );
+    DnsRecord::new_tlsa_from_der(name, 3, 2, 1, b"abc").unwrap_err();
+    DnsRecord::new_tlsa_from_der(name, 3, 1, 3, b"abc").unwrap_err();
+    DnsRecord::new_tlsa_from_der(name, 3, 0, 1, b"").unwrap_err();
+}
+
+#[cfg(test)]
+#[test]
+fn test_sshfp() {
+    let sha256 = b"\xC2\xD3\x59\x17\x85\xB9\xFD\x07\xB9\x09\xAD\x64\x5D\x3D\x96\xD8\
+        \xB8\x91\xC5\x4C\xD7\xD6\xB1\xE7\x5A\xCA\x4D\xAC\x82\x65\x97\x35";
+    let record = DnsRecord::new_sshfp(
+        "edge1.example.com",
+        DnsRecord::SSHFP_ALGORITHM_ED25519,
+        DnsRecord::SSHFP_TYPE_SHA256,
+        sha256,
+    )
+    .unwrap();
+    assert_eq!(
+        DnsRecord::SSHFP {
+            name: DnsName::new("edge1.example.com").unwrap(),
+            class: DnsClass::Internet,
+            ttl: 300,
+            algorithm: 4,
+            fingerprint_type: 2,
+            fingerprint: sha256.to_vec(),
+        },
+        record
+    );
+    assert_eq!(DnsType::SSHFP, record.typ());
+    assert_eq!(
+        "DnsRecord::SSHFP(edge1.example.com,4,2,\
+        C2D3591785B9FD07B909AD645D3D96D8B891C54CD7D6B1E75ACA4DAC82659735)",
+        format!("{record:?}")
+    );
+    assert_eq!(
+        Ok(&record),
+        DnsRecord::parse_sshfp(
+            "edge1.example.com",
+            "4 2 c2d3591785b9fd07b909ad645d3d96d8b891c54cd7d6b1e75aca4dac82659735"
+        )
+        .as_ref()
+    );
+    DnsRecord::new_sshfp("a", 4, 2, &sha256[1..]).unwrap_err();
+    DnsRecord::new_sshfp("a", 4, 1, sha256).unwrap_err();
+    DnsRecord::new_sshfp("a", 4, 0, b"").unwrap_err();
+    DnsRecord::new_sshfp("a", 255, 255, b"\x01").unwrap();
+    DnsRecord::parse_sshfp("a", "4").unwrap_err();
+    DnsRecord::parse_sshfp("a", "4 2 0").unwrap_err();
+    // Wire format.
+    let record = DnsRecord::new_sshfp("a.b", 1, 0, b"\x12\x34").unwrap();
+    let mut out: FixedBuf<64> = FixedBuf::new();
+    record.write(&mut out).unwrap();
+    assert_eq!(
+        b"\x01a\x01b\x00\x00\x2C\x00\x01\x00\x00\x01\x2C\x00\x04\x01\x00\x12\x34",
+        out.readable()
+    );
+    assert_eq!(record, DnsRecord::read(&mut out).unwrap());
+    let mut buf: FixedBuf<64> = FixedBuf::new();
+    buf.write_bytes(b"\x01a\x00\x00\x2C\x00\x01\x00\x00\x01\x2C\x00\x01\x04")
+        .unwrap();
+    assert_eq!(Err(DnsError::Truncated), DnsRecord::read(&mut buf));
+}
+
+#[cfg(all(test, feature = "sha1", feature = "sha2"))]
+#[test]
+fn test_sshfp_from_public_key() {
+    // Expected values are from `ssh-keygen -r edge1.example.com -f key.pub`.
+    let fingerprints = |public_key: &str| {
+        DnsRecord::new_sshfp_from_public_key("edge1.example.com", public_key).map(|records| {
+            records
+                .iter()
+                .map(|record| format!("{record:?}"))
+                .collect::<Vec<String>>()
+        })
+    };
+    assert_eq!(
+        Ok(vec![
+            "DnsRecord::SSHFP(edge1.example.com,4,1,99F81890D9616BF2566B0AD7CF4DEF8F09D3BEEC)"
+                .to_string(),
+            "DnsRecord::SSHFP(edge1.example.com,4,2,\
+            C2D3591785B9FD07B909AD645D3D96D8B891C54CD7D6B1E75ACA4DAC82659735)"
+                .to_string(),
+        ]),
+        fingerprints(
+            "ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAII67cDeeZzTZfrUfrBcoGzvp+8ICmYxi3Rd5RnDGbZ90 \
+            root@edge1\n"
+        )
+    );
+    assert_eq!(
+        Ok(vec![
+            "DnsRecord::SSHFP(edge1.example.com,3,1,C1DE0AEC4402F42736A1FAA956AFB36F18635653)"
+                .to_string(),
+            "DnsRecord::SSHFP(edge1.example.com,3,2,\
+            F363CD45FC9F954733A1FCC6E6351AD12E6721238D48BF42159A83D3A2A70B1A)"
+                .to_string(),
+        ]),
+        fingerprints(
+            "ecdsa-sha2-nistp256 AAAAE2VjZHNhLXNoYTItbmlzdHAyNTYAAAAIbmlzdHAyNTYAAABBBOfsxPQemxa\
+            AYnPKG/BVg893lqXYJ+Q1YkNxvSC4LRFcNIyjK9bGcA+udrRMOoseh82wJoCa73AUMUW/AyZ7G7k="
+        )
+    );
+    assert_eq!(
+        Ok(vec![
+            "DnsRecord::SSHFP(edge1.example.com,1,1,697B931E628B7128A1440FA4C39467AD341E2131)"
+                .to_string(),
+            "DnsRecord::SSHFP(edge1.example.com,1,2,\
+            7877E59EFB98F2A6481B59FBA4BC9571DBE62E6E5AC9A96920A0C117F7B08702)"
+                .to_string(),
+        ]),
+        fingerprints(
+            "ssh-rsa AAAAB3NzaC1yc2EAAAADAQABAAAAgQC29ySjEK3lN49ex3dIBVCm35xNKQpupUCsgcswtFhm6VIUW\
+            ABaodFQY8zpZgVY2w95YkYssIY0AEWIaDXNsEtV2GmcCg3k4fWJbzL3CWwJuz79vnvl0IZQ8SwHz8e2Yc7MvP\
+            D1TkOojLduRT+PiBTR19Qkd6Zwb0x/HCT5smL0sQ== root@edge1"
+        )
+    );
+    for public_key in [
+        "",
+        "ssh-ed25519",
+        "ssh-dss AAAAB3NzaC1kc3MAAAA=",
+        "ssh-ed25519 not-base64",
+        // The blob has key type ssh-rsa.
+        "ssh-ed25519 AAAAB3NzaC1yc2EAAAADAQAB",
+        // The blob's key type length is too long.
+        "ssh-ed25519 AAAAD3NzaC1lZDI1NTE5",
+    ] {
+        fingerprints(public_key).unwrap_err();
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_dname() {
+    let record = DnsRecord::new_dname("old.example.com", "new.example.com").unwrap();
+    assert_eq!(
+        DnsRecord::DNAME {
+            name: DnsName::new("old.example.com").unwrap(),
+            class: DnsClass::Internet,
+            ttl: 300,
+            target: DnsName::new("new.example.com").unwrap(),
+        },
+        record
+    );
+    assert_eq!(DnsType::DNAME, record.typ());
+    assert_eq!(
+        "DnsRecord::DNAME(old.example.com,new.example.com)",
+        format!("{record:?}")
+    );
+    DnsRecord::new_dname("a..b", "c").unwrap_err();
+    DnsRecord::new_dname("a", "c..d").unwrap_err();
+    // The target is not compressed.
+    let record = DnsRecord::new_dname("a.b", "c.a.b").unwrap();
+    let mut out: FixedBuf<64> = FixedBuf::new();
+    let mut compressor = DnsNameCompressor::new(&mut out);
+    record.write_compressed(&mut out, &mut compressor).unwrap();
+    assert_eq!(
+        b"\x01a\x01b\x00\x00\x27\x00\x01\x00\x00\x01\x2C\x00\x07\x01c\x01a\x01b\x00",
+        out.readable()
+    );
+    assert_eq!(record, DnsRecord::read(&mut out).unwrap());
+    check_rdlength(&record);
+}
+
+#[cfg(test)]
+#[test]
+fn test_naptr() {
+    // https://datatracker.ietf.org/doc/html/rfc6116#section-3.4.3
+    let record = DnsRecord::new_naptr(
+        "4.3.2.1.5.5.5.0.0.8.1.e164.arpa",
+        100,
+        10,
+        "u",
+        "E2U+sip",
+        "!^.*$!sip:info@example.com!",
+        ".",
+    )
+    .unwrap();
+    assert_eq!(
+        DnsRecord::NAPTR {
+            name: DnsName::new("4.3.2.1.5.5.5.0.0.8.1.e164.arpa").unwrap(),
+            class: DnsClass::Internet,
+            ttl: 300,
+            order: 100,
+            preference: 10,
+            flags: b"u".to_vec(),
+            service: b"E2U+sip".to_vec(),
+            regexp: b"!^.*$!sip:info@example.com!".to_vec(),
+            replacement: DnsName::root(),
+        },
+        record
+    );
+    assert_eq!(DnsType::NAPTR, record.typ());
+    assert_eq!(
+        "DnsRecord::NAPTR(4.3.2.1.5.5.5.0.0.8.1.e164.arpa,100,10,\"u\",\"E2U+sip\",\
+        \"!^.*$!sip:info@example.com!\",.)",
+        format!("{record:?}")
+    );
+    assert_eq!(
+        Ok(&record),
+        DnsRecord::parse_naptr(
+            "4.3.2.1.5.5.5.0.0.8.1.e164.arpa",
+            "100 10 \"u\" \"E2U+sip\" \"!^.*$!sip:info@example.com!\" ."
+        )
+        .as_ref()
+    );
+    // https://datatracker.ietf.org/doc/html/rfc3403#section-6.2
+    let record = DnsRecord::parse_naptr(
+        "foo.com",
+        "100 50 \"s\" \"http+I2L+I2C+I2R\" \"\" _http._tcp.foo.com",
+    )
+    .unwrap();
+    assert_eq!(
+        "DnsRecord::NAPTR(foo.com,100,50,\"s\",\"http+I2L+I2C+I2R\",\"\",_http._tcp.foo.com)",
+        format!("{record:?}")
+    );
+    for regexp in [
+        "/a/b/",
+        "/a/b/i",
+        "!^\\+1(.*)$!sip:\\1@example.com!",
+        "#a\\#b#c\\#d#",
+        "/a//",
+    ] {
+        DnsRecord::new_naptr("a", 1, 1, "u", "E2U+sip", regexp, ".").unwrap();
+    }
+    for regexp in [
+        "/", "/a", "/a/b", "/a/b/c/", "/a/b/x", "/a/b/ii", "//b/", "1a1b1", "iaibi", "\\a\\b\\",
+        "/a/b\\/",
+    ] {
+        DnsRecord::new_naptr("a", 1, 1, "u", "E2U+sip", regexp, ".").unwrap_err();
+    }
+    DnsRecord::new_naptr("a", 1, 1, "u", "E2U+sip", "/a/b/", "b").unwrap_err();
+    DnsRecord::new_naptr("a", 1, 1, "u!", "E2U+sip", "/a/b/", ".").unwrap_err();
+    DnsRecord::new_naptr("a", 1, 1, "u", &"x".repeat(256), "", "b").unwrap_err();
+    DnsRecord::new_naptr("a", 1, 1, "u", "E2U+sip", "", "b..c").unwrap_err();
+    DnsRecord::parse_naptr("a", "1 1 \"u\" \"E2U+sip\" \"/a/b/\"").unwrap_err();
+    DnsRecord::parse_naptr("a", "1 x \"u\" \"E2U+sip\" \"/a/b/\" .").unwrap_err();
+    // Wire format, with an uncompressed replacement.
+    let record = DnsRecord::new_naptr("a.b", 1, 2, "s", "x", "", "c.a.b").unwrap();
+    let mut out: FixedBuf<64> = FixedBuf::new();
+    let mut compressor = DnsNameCompressor::new(&mut out);
+    record.write_compressed(&mut out, &mut compressor).unwrap();
+    assert_eq!(
+        b"\x01a\x01b\x00\x00\x23\x00\x01\x00\x00\x01\x2C\x00\x10\x00\x01\x00\x02\
+          \x01s\x01x\x00\x01c\x01a\x01b\x00",
+        out.readable()
+    );
+    assert_eq!(record, DnsRecord::read(&mut out).unwrap());
+    check_rdlength(&record);
+    // Keeps strings that are not UTF-8.
+    let bytes =
+        b"\x01a\x00\x00\x23\x00\x01\x00\x00\x01\x2C\x00\x09\x00\x01\x00\x02\x01\xFF\x00\x00\x00";
+    let mut buf: FixedBuf<64> = FixedBuf::new();
+    buf.write_bytes(bytes).unwrap();
+    let record = DnsRecord::read(&mut buf).unwrap();
+    assert_eq!(
+        "DnsRecord::NAPTR(a,1,2,\"\\255\",\"\",\"\",.)",
+        format!("{record:?}")
+    );
+    let mut out: FixedBuf<64> = FixedBuf::new();
+    record.write(&mut out).unwrap();
+    assert_eq!(bytes, out.readable());
+}
+
+#[cfg(test)]
+#[test]
+fn test_uri() {
+    let record =
+        DnsRecord::new_uri("_sip._udp.example.com", 10, 1, "sip:info@example.com").unwrap();
+    assert_eq!(
+        DnsRecord::URI {
+            name: DnsName::new("_sip._udp.example.com").unwrap(),
+            class: DnsClass::Internet,
+            ttl: 300,
+            priority: 10,
+            weight: 1,
+            target: "sip:info@example.com".to_string(),
+        },
+        record
+    );
+    assert_eq!(DnsType::URI, record.typ());
+    assert_eq!(
+        "DnsRecord::URI(_sip._udp.example.com,10,1,\"sip:info@example.com\")",
+        format!("{record:?}")
+    );
+    assert_eq!(
+        Ok(&record),
+        DnsRecord::parse_uri("_sip._udp.example.com", "10 1 \"sip:info@example.com\"").as_ref()
+    );
+    DnsRecord::new_uri(
+        "_ftp._tcp.example.com",
+        10,
+        1,
+        "ftp://ftp1.example.com/public",
+    )
+    .unwrap();
+    for target in ["", "info@example.com", ":x", "1a:b", "a b:c"] {
+        DnsRecord::new_uri("a", 1, 1, target).unwrap_err();
+    }
+    DnsRecord::parse_uri("a", "10 \"sip:a\"").unwrap_err();
+    DnsRecord::parse_uri("a", "10 1 sip:a b").unwrap_err();
+    // Wire format.
+    let record = DnsRecord::new_uri("a.b", 1, 2, "x:y").unwrap();
+    let mut out: FixedBuf<64> = FixedBuf::new();
+    record.write(&mut out).unwrap();
+    assert_eq!(
+        b"\x01a\x01b\x00\x01\x00\x00\x01\x00\x00\x01\x2C\x00\x07\x00\x01\x00\x02x:y",
+        out.readable()
+    );
+    assert_eq!(record, DnsRecord::read(&mut out).unwrap());
+    let mut buf: FixedBuf<64> = FixedBuf::new();
+    buf.write_bytes(b"\x01a\x00\x01\x00\x00\x01\x00\x00\x01\x2C\x00\x04\x00\x01\x00\x02")
+        .unwrap();
+    assert_eq!(Err(DnsError::InvalidRdata), DnsRecord::read(&mut buf));
+}
+
+/// Checks that reading `record` fails when its RDLENGTH is one byte too short or too long.
+#[cfg(test)]
+fn check_rdlength(record: &DnsRecord) {
+    let mut out: FixedBuf<512> = FixedBuf::new();
+    record.write(&mut out).unwrap();
+    let bytes = out.readable().to_vec();
+    let rdlength_pos = record.name().as_bytes().unwrap().len() + 8;
+    let rdlength = u16::from_be_bytes([bytes[rdlength_pos], bytes[rdlength_pos + 1]]);
+    for (rdlength, expected) in [
+        (rdlength - 1, DnsError::Truncated),
+        (rdlength + 1, DnsError::InvalidRdata),
+    ] {
+        let mut buf: FixedBuf<512> = FixedBuf::new();
+        buf.write_bytes(&bytes[..rdlength_pos]).unwrap();
+        buf.write_bytes(&rdlength.to_be_bytes()).unwrap();
+        buf.write_bytes(&bytes[rdlength_pos + 2..]).unwrap();
+        // A trailing byte of RDATA, or the start of the next record.
+        buf.write_bytes(&[0]).unwrap();
+        assert_eq!(Err(expected), DnsRecord::read(&mut buf), "{record:?}");
+    }
 }
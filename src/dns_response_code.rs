@@ -14,6 +14,10 @@
 /// > - `6-15` Reserved for future use.
 ///
 /// <https://datatracker.ietf.org/doc/html/rfc1035#section-4.1.1>
+///
+/// > YXDOMAIN   6  Some name that ought not to exist, does exist.
+///
+/// <https://datatracker.ietf.org/doc/html/rfc2136#section-2.2>
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub enum DnsResponseCode {
     NoError,
@@ -22,6 +26,10 @@ pub enum DnsResponseCode {
     NameError,
     NotImplemented,
     Refused,
+    /// Servers also return this when DNAME substitution makes a name that is too long.
+    ///
+    /// <https://datatracker.ietf.org/doc/html/rfc6672#section-2.2>
+    YxDomain,
     Reserved(u8),
 }
 impl DnsResponseCode {
@@ -34,6 +42,7 @@ impl DnsResponseCode {
             3 => DnsResponseCode::NameError,
             4 => DnsResponseCode::NotImplemented,
             5 => DnsResponseCode::Refused,
+            6 => DnsResponseCode::YxDomain,
             other => DnsResponseCode::Reserved(other),
         }
     }
@@ -47,6 +56,7 @@ impl DnsResponseCode {
             DnsResponseCode::NameError => 3,
             DnsResponseCode::NotImplemented => 4,
             DnsResponseCode::Refused => 5,
+            DnsResponseCode::YxDomain => 6,
             DnsResponseCode::Reserved(other) => *other,
         }
     }
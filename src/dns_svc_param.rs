@@ -0,0 +1,471 @@
+use crate::dns_presentation::base64_encode;
+use crate::{read_u16_be, DnsError};
+use core::fmt::{Display, Formatter};
+use fixed_buffer::FixedBuf;
+use std::convert::TryFrom;
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+/// A service parameter of an SVCB or HTTPS record.
+///
+/// > SvcParams: A collection of key=value pairs.  SvcParams are used to convey additional
+/// > information to clients.
+///
+/// <https://datatracker.ietf.org/doc/html/rfc9460#section-2.1>
+///
+/// > The RDATA for the SVCB RR consists of:
+/// >
+/// > - a 2-octet field for SvcPriority as an integer in network byte order.
+/// > - the uncompressed, fully qualified TargetName, represented as a sequence of length-prefixed
+/// >   labels per Section 3.1 of [RFC1035].
+/// > - the SvcParams, consuming the remainder of the record (so smaller than 65535 octets and
+/// >   constrained by the RDATA and DNS message sizes).
+/// >
+/// > When the list of SvcParams is non-empty, it contains a series of SvcParamKey=SvcParamValue
+/// > pairs, represented as:
+/// >
+/// > - a 2-octet field containing the SvcParamKey as an integer in network byte order.
+/// > - a 2-octet field containing the length of the SvcParamValue as an integer between 0 and
+/// >   65535 in network byte order.
+/// > - an octet string of this length whose contents are the SvcParamValue in a format
+/// >   determined by the SvcParamKey.
+/// >
+/// > SvcParamKeys SHALL appear in increasing numeric order.
+///
+/// <https://datatracker.ietf.org/doc/html/rfc9460#section-2.2>
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum DnsSvcParam {
+    /// Keys that clients must understand to use the record.
+    ///
+    /// <https://datatracker.ietf.org/doc/html/rfc9460#section-8>
+    Mandatory(Vec<u16>),
+    /// Application-Layer Protocol Negotiation (ALPN) protocol IDs, like `h3` and `h2`.
+    ///
+    /// <https://datatracker.ietf.org/doc/html/rfc9460#section-7.1>
+    Alpn(Vec<Vec<u8>>),
+    /// The service does not support the scheme's default protocol, like `http/1.1` for HTTPS.
+    ///
+    /// <https://datatracker.ietf.org/doc/html/rfc9460#section-7.1>
+    NoDefaultAlpn,
+    /// The TCP or UDP port of the service.
+    ///
+    /// <https://datatracker.ietf.org/doc/html/rfc9460#section-7.2>
+    Port(u16),
+    /// IPv4 addresses that clients may use to reach the service.
+    ///
+    /// <https://datatracker.ietf.org/doc/html/rfc9460#section-7.3>
+    Ipv4Hint(Vec<Ipv4Addr>),
+    /// A TLS Encrypted ClientHello `ECHConfigList`.
+    Ech(Vec<u8>),
+    /// IPv6 addresses that clients may use to reach the service.
+    ///
+    /// <https://datatracker.ietf.org/doc/html/rfc9460#section-7.3>
+    Ipv6Hint(Vec<Ipv6Addr>),
+    /// A key that this library does not know and its value.
+    Unknown(u16, Vec<u8>),
+}
+impl DnsSvcParam {
+    pub const MANDATORY: u16 = 0;
+    pub const ALPN: u16 = 1;
+    pub const NO_DEFAULT_ALPN: u16 = 2;
+    pub const PORT: u16 = 3;
+    pub const IPV4HINT: u16 = 4;
+    pub const ECH: u16 = 5;
+    pub const IPV6HINT: u16 = 6;
+    /// Reserved as an invalid key.
+    ///
+    /// <https://datatracker.ietf.org/doc/html/rfc9460#section-14.3.2>
+    pub const INVALID_KEY: u16 = 65535;
+
+    #[must_use]
+    pub fn key(&self) -> u16 {
+        match self {
+            DnsSvcParam::Mandatory(_) => Self::MANDATORY,
+            DnsSvcParam::Alpn(_) => Self::ALPN,
+            DnsSvcParam::NoDefaultAlpn => Self::NO_DEFAULT_ALPN,
+            DnsSvcParam::Port(_) => Self::PORT,
+            DnsSvcParam::Ipv4Hint(_) => Self::IPV4HINT,
+            DnsSvcParam::Ech(_) => Self::ECH,
+            DnsSvcParam::Ipv6Hint(_) => Self::IPV6HINT,
+            DnsSvcParam::Unknown(key, _) => *key,
+        }
+    }
+
+    /// Returns the wire format SvcParamValue.
+    /// Returns `None` when an ALPN ID is longer than 255 bytes.
+    fn value(&self) -> Option<Vec<u8>> {
+        Some(match self {
+            DnsSvcParam::Mandatory(keys) => keys.iter().flat_map(|key| key.to_be_bytes()).collect(),
+            DnsSvcParam::Alpn(ids) => {
+                let mut value = Vec::new();
+                for id in ids {
+                    value.push(u8::try_from(id.len()).ok()?);
+                    value.extend_from_slice(id);
+                }
+                value
+            }
+            DnsSvcParam::NoDefaultAlpn => Vec::new(),
+            DnsSvcParam::Port(port) => port.to_be_bytes().to_vec(),
+            DnsSvcParam::Ipv4Hint(addrs) => addrs.iter().flat_map(Ipv4Addr::octets).collect(),
+            DnsSvcParam::Ech(config_list) => config_list.clone(),
+            DnsSvcParam::Ipv6Hint(addrs) => addrs.iter().flat_map(Ipv6Addr::octets).collect(),
+            DnsSvcParam::Unknown(_, value) => value.clone(),
+        })
+    }
+
+    /// Parses a wire format SvcParamValue.
+    /// Returns `None` when `value` is not valid for `key`.
+    fn from_wire(key: u16, value: &[u8]) -> Option<Self> {
+        match key {
+            Self::MANDATORY => {
+                if !value.len().is_multiple_of(2) {
+                    return None;
+                }
+                Some(DnsSvcParam::Mandatory(
+                    value
+                        .chunks(2)
+                        .map(|b| u16::from_be_bytes([b[0], b[1]]))
+                        .collect(),
+                ))
+            }
+            Self::ALPN => {
+                let mut ids = Vec::new();
+                let mut rest = value;
+                while let Some((len, tail)) = rest.split_first() {
+                    let len = usize::from(*len);
+                    if tail.len() < len {
+                        return None;
+                    }
+                    ids.push(tail[..len].to_vec());
+                    rest = &tail[len..];
+                }
+                Some(DnsSvcParam::Alpn(ids))
+            }
+            Self::NO_DEFAULT_ALPN if value.is_empty() => Some(DnsSvcParam::NoDefaultAlpn),
+            Self::NO_DEFAULT_ALPN => None,
+            Self::PORT => Some(DnsSvcParam::Port(u16::from_be_bytes(
+                <[u8; 2]>::try_from(value).ok()?,
+            ))),
+            Self::IPV4HINT if value.len().is_multiple_of(4) => Some(DnsSvcParam::Ipv4Hint(
+                value
+                    .chunks(4)
+                    .map(|b| Ipv4Addr::new(b[0], b[1], b[2], b[3]))
+                    .collect(),
+            )),
+            Self::IPV6HINT if value.len().is_multiple_of(16) => Some(DnsSvcParam::Ipv6Hint(
+                value
+                    .chunks(16)
+                    .filter_map(|b| <[u8; 16]>::try_from(b).ok())
+                    .map(Ipv6Addr::from)
+                    .collect(),
+            )),
+            Self::IPV4HINT | Self::IPV6HINT => None,
+            Self::ECH => Some(DnsSvcParam::Ech(value.to_vec())),
+            _ => Some(DnsSvcParam::Unknown(key, value.to_vec())),
+        }
+    }
+
+    /// Sorts `params` and the keys in any `mandatory` param into the order that RFC 9460 requires.
+    pub(crate) fn sort(params: &mut [Self]) {
+        params.sort_by_key(Self::key);
+        for param in params {
+            if let DnsSvcParam::Mandatory(keys) = param {
+                keys.sort_unstable();
+            }
+        }
+    }
+
+    /// Checks that `params` follow the rules of RFC 9460:
+    /// - Keys appear once each, in increasing order.
+    /// - `mandatory` lists one or more keys in increasing order, not including itself,
+    ///   and each of them appears in `params`.
+    /// - `alpn` has one or more IDs, each 1 to 255 bytes long.
+    /// - `no-default-alpn` appears only with `alpn`.
+    /// - `ipv4hint` and `ipv6hint` have one or more addresses.
+    /// - Values are at most 65,535 bytes long.
+    /// - Key 65535 does not appear.
+    ///
+    /// <https://datatracker.ietf.org/doc/html/rfc9460#section-2.2>
+    ///
+    /// <https://datatracker.ietf.org/doc/html/rfc9460#section-7>
+    ///
+    /// <https://datatracker.ietf.org/doc/html/rfc9460#section-8>
+    ///
+    /// # Errors
+    /// Returns an error describing the first rule that `params` break.
+    pub(crate) fn validate(params: &[Self]) -> Result<(), String> {
+        let keys: Vec<u16> = params.iter().map(Self::key).collect();
+        if keys.windows(2).any(|pair| pair[0] >= pair[1]) {
+            return Err(format!(
+                "SvcParamKeys are not in strictly increasing order: {keys:?}"
+            ));
+        }
+        for param in params {
+            match param {
+                DnsSvcParam::Mandatory(mandatory) => {
+                    if mandatory.is_empty() {
+                        return Err("mandatory has no keys".to_string());
+                    }
+                    if mandatory.windows(2).any(|pair| pair[0] >= pair[1]) {
+                        return Err(format!(
+                            "mandatory keys are not in strictly increasing order: {mandatory:?}"
+                        ));
+                    }
+                    if mandatory.contains(&Self::MANDATORY) {
+                        return Err("mandatory lists itself".to_string());
+                    }
+                    if let Some(missing) = mandatory.iter().find(|key| !keys.contains(key)) {
+                        return Err(format!("mandatory key {missing} is missing"));
+                    }
+                }
+                DnsSvcParam::Alpn(ids)
+                    if ids.is_empty() || ids.iter().any(|id| id.is_empty() || id.len() > 255) =>
+                {
+                    return Err(format!("not a valid alpn: {param}"));
+                }
+                DnsSvcParam::NoDefaultAlpn if !keys.contains(&Self::ALPN) => {
+                    return Err("no-default-alpn without alpn".to_string());
+                }
+                DnsSvcParam::Ipv4Hint(addrs) if addrs.is_empty() => {
+                    return Err("ipv4hint has no addresses".to_string());
+                }
+                DnsSvcParam::Ipv6Hint(addrs) if addrs.is_empty() => {
+                    return Err("ipv6hint has no addresses".to_string());
+                }
+                DnsSvcParam::Unknown(key, _) if *key <= Self::IPV6HINT => {
+                    return Err(format!("unknown param has known key {key}"));
+                }
+                DnsSvcParam::Unknown(Self::INVALID_KEY, _) => {
+                    return Err("key65535 is not allowed".to_string());
+                }
+                _ => {}
+            }
+            if param
+                .value()
+                .is_none_or(|value| u16::try_from(value.len()).is_err())
+            {
+                return Err(format!("SvcParamValue is too long: {}", param.key()));
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads SvcParams until `len` bytes of `buf` are consumed.
+    ///
+    /// # Errors
+    /// Returns an error when `buf` does not contain `len` bytes of well-formed SvcParams.
+    pub(crate) fn read_params<const N: usize>(
+        buf: &mut FixedBuf<N>,
+        len: usize,
+    ) -> Result<Vec<Self>, DnsError> {
+        let mut params = Vec::new();
+        let mut consumed = 0;
+        while consumed < len {
+            let key = read_u16_be(buf)?;
+            let mut value = vec![0_u8; usize::from(read_u16_be(buf)?)];
+            buf.try_read_exact(&mut value).ok_or(DnsError::Truncated)?;
+            consumed += 4 + value.len();
+            params.push(Self::from_wire(key, &value).ok_or(DnsError::InvalidRdata)?);
+        }
+        if consumed != len {
+            return Err(DnsError::Truncated);
+        }
+        Ok(params)
+    }
+
+    /// Appends the wire format of `params` to `out`.
+    ///
+    /// # Errors
+    /// Returns an error when a value is longer than 65,535 bytes.
+    pub(crate) fn write_params(params: &[Self], out: &mut Vec<u8>) -> Result<(), DnsError> {
+        for param in params {
+            let err = || DnsError::Internal(format!("SvcParamValue is too long: {}", param.key()));
+            let value = param.value().ok_or_else(err)?;
+            let len = u16::try_from(value.len()).map_err(|_| err())?;
+            out.extend_from_slice(&param.key().to_be_bytes());
+            out.extend_from_slice(&len.to_be_bytes());
+            out.extend_from_slice(&value);
+        }
+        Ok(())
+    }
+
+    fn key_name(key: u16) -> String {
+        match key {
+            Self::MANDATORY => "mandatory".to_string(),
+            Self::ALPN => "alpn".to_string(),
+            Self::NO_DEFAULT_ALPN => "no-default-alpn".to_string(),
+            Self::PORT => "port".to_string(),
+            Self::IPV4HINT => "ipv4hint".to_string(),
+            Self::ECH => "ech".to_string(),
+            Self::IPV6HINT => "ipv6hint".to_string(),
+            other => format!("key{other}"),
+        }
+    }
+}
+/// Writes the presentation format, like `alpn=h3,h2` or `port=8443`.
+///
+/// <https://datatracker.ietf.org/doc/html/rfc9460#section-2.1>
+impl Display for DnsSvcParam {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        fn write_escaped(f: &mut Formatter<'_>, bytes: &[u8]) -> Result<(), core::fmt::Error> {
+            for b in bytes {
+                match b {
+                    b',' | b'\\' | b'"' => write!(f, "\\{}", char::from(*b))?,
+                    0x21..=0x7E => write!(f, "{}", char::from(*b))?,
+                    _ => write!(f, "\\{b:03}")?,
+                }
+            }
+            Ok(())
+        }
+        write!(f, "{}", Self::key_name(self.key()))?;
+        match self {
+            DnsSvcParam::Mandatory(keys) => {
+                let names: Vec<String> = keys.iter().copied().map(Self::key_name).collect();
+                write!(f, "={}", names.join(","))
+            }
+            DnsSvcParam::Alpn(ids) => {
+                write!(f, "=")?;
+                for (n, id) in ids.iter().enumerate() {
+                    if n > 0 {
+                        write!(f, ",")?;
+                    }
+                    write_escaped(f, id)?;
+                }
+                Ok(())
+            }
+            DnsSvcParam::NoDefaultAlpn => Ok(()),
+            DnsSvcParam::Port(port) => write!(f, "={port}"),
+            DnsSvcParam::Ipv4Hint(addrs) => {
+                let addrs: Vec<String> = addrs.iter().map(Ipv4Addr::to_string).collect();
+                write!(f, "={}", addrs.join(","))
+            }
+            DnsSvcParam::Ech(config_list) => write!(f, "={}", base64_encode(config_list)),
+            DnsSvcParam::Ipv6Hint(addrs) => {
+                let addrs: Vec<String> = addrs.iter().map(Ipv6Addr::to_string).collect();
+                write!(f, "={}", addrs.join(","))
+            }
+            DnsSvcParam::Unknown(_, value) if value.is_empty() => Ok(()),
+            DnsSvcParam::Unknown(_, value) => {
+                write!(f, "=")?;
+                write_escaped(f, value)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_display() {
+    assert_eq!(
+        "mandatory=alpn,port",
+        DnsSvcParam::Mandatory(vec![1, 3]).to_string()
+    );
+    assert_eq!(
+        "alpn=h3,h2,a\\,b",
+        DnsSvcParam::Alpn(vec![b"h3".to_vec(), b"h2".to_vec(), b"a,b".to_vec()]).to_string()
+    );
+    assert_eq!("no-default-alpn", DnsSvcParam::NoDefaultAlpn.to_string());
+    assert_eq!("port=8443", DnsSvcParam::Port(8443).to_string());
+    assert_eq!(
+        "ipv4hint=192.0.2.1,192.0.2.2",
+        DnsSvcParam::Ipv4Hint(vec![
+            Ipv4Addr::new(192, 0, 2, 1),
+            Ipv4Addr::new(192, 0, 2, 2)
+        ])
+        .to_string()
+    );
+    assert_eq!("ech=AQID", DnsSvcParam::Ech(vec![1, 2, 3]).to_string());
+    assert_eq!(
+        "ipv6hint=2001:db8::1",
+        DnsSvcParam::Ipv6Hint(vec!["2001:db8::1".parse().unwrap()]).to_string()
+    );
+    assert_eq!(
+        "key667=hello\\210",
+        DnsSvcParam::Unknown(667, b"hello\xD2".to_vec()).to_string()
+    );
+    assert_eq!("key667", DnsSvcParam::Unknown(667, Vec::new()).to_string());
+}
+
+#[cfg(test)]
+#[test]
+fn test_validate() {
+    use DnsSvcParam::{Alpn, Ipv4Hint, Ipv6Hint, Mandatory, NoDefaultAlpn, Port, Unknown};
+    let alpn = || Alpn(vec![b"h3".to_vec()]);
+    DnsSvcParam::validate(&[]).unwrap();
+    DnsSvcParam::validate(&[Mandatory(vec![1, 3]), alpn(), NoDefaultAlpn, Port(443)]).unwrap();
+    DnsSvcParam::validate(&[Unknown(7, Vec::new()), Unknown(65534, b"x".to_vec())]).unwrap();
+    for params in [
+        vec![Port(443), alpn()],
+        vec![Port(443), Port(443)],
+        vec![Mandatory(Vec::new()), Port(443)],
+        vec![Mandatory(vec![3, 1]), alpn(), Port(443)],
+        vec![Mandatory(vec![0, 3]), Port(443)],
+        vec![Mandatory(vec![3]), alpn()],
+        vec![Alpn(Vec::new())],
+        vec![Alpn(vec![Vec::new()])],
+        vec![Alpn(vec![vec![b'a'; 256]])],
+        vec![NoDefaultAlpn],
+        vec![Ipv4Hint(Vec::new())],
+        vec![Ipv6Hint(Vec::new())],
+        vec![Unknown(3, b"\x01\xBB".to_vec())],
+        vec![Unknown(65535, Vec::new())],
+    ] {
+        DnsSvcParam::validate(&params).unwrap_err();
+    }
+    let mut params = vec![Port(443), Mandatory(vec![3, 1]), alpn()];
+    DnsSvcParam::sort(&mut params);
+    assert_eq!(vec![Mandatory(vec![1, 3]), alpn(), Port(443)], params);
+    DnsSvcParam::validate(&params).unwrap();
+}
+
+#[cfg(test)]
+#[test]
+fn test_wire() {
+    let params = vec![
+        DnsSvcParam::Mandatory(vec![1]),
+        DnsSvcParam::Alpn(vec![b"h3".to_vec(), b"h2".to_vec()]),
+        DnsSvcParam::NoDefaultAlpn,
+        DnsSvcParam::Port(443),
+        DnsSvcParam::Ipv4Hint(vec![Ipv4Addr::new(192, 0, 2, 1)]),
+        DnsSvcParam::Ech(vec![0xAB]),
+        DnsSvcParam::Ipv6Hint(vec!["2001:db8::1".parse().unwrap()]),
+        DnsSvcParam::Unknown(667, b"x".to_vec()),
+    ];
+    let mut bytes = Vec::new();
+    DnsSvcParam::write_params(&params, &mut bytes).unwrap();
+    assert_eq!(
+        &b"\x00\x00\x00\x02\x00\x01\
+        \x00\x01\x00\x06\x02h3\x02h2\
+        \x00\x02\x00\x00\
+        \x00\x03\x00\x02\x01\xBB\
+        \x00\x04\x00\x04\xC0\x00\x02\x01\
+        \x00\x05\x00\x01\xAB\
+        \x00\x06\x00\x10\x20\x01\x0D\xB8\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x01\
+        \x02\x9B\x00\x01x"[..],
+        bytes.as_slice()
+    );
+    let mut buf: FixedBuf<128> = FixedBuf::new();
+    buf.write_bytes(&bytes).unwrap();
+    assert_eq!(Ok(params), DnsSvcParam::read_params(&mut buf, bytes.len()));
+    for bytes in [
+        &b"\x00\x00\x00\x01\x00"[..],
+        b"\x00\x01\x00\x03\x03h2",
+        b"\x00\x02\x00\x01\x00",
+        b"\x00\x03\x00\x01\x01",
+        b"\x00\x04\x00\x03\x01\x02\x03",
+        b"\x00\x06\x00\x04\x01\x02\x03\x04",
+    ] {
+        let mut buf: FixedBuf<128> = FixedBuf::new();
+        buf.write_bytes(bytes).unwrap();
+        assert_eq!(
+            Err(DnsError::InvalidRdata),
+            DnsSvcParam::read_params(&mut buf, bytes.len()),
+            "{bytes:?}"
+        );
+    }
+    let mut buf: FixedBuf<128> = FixedBuf::new();
+    buf.write_bytes(b"\x00\x03\x00\x02\x01\xBB").unwrap();
+    assert_eq!(
+        Err(DnsError::Truncated),
+        DnsSvcParam::read_params(&mut buf, 5)
+    );
+}
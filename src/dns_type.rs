@@ -1,3 +1,4 @@
+use crate::dns_presentation::parse_number;
 use crate::{read_u16_be, write_u16_be, DnsError};
 use core::fmt::{Display, Formatter};
 use fixed_buffer::FixedBuf;
@@ -33,6 +34,42 @@ pub enum DnsType {
     SOA,
     /// Text string
     TXT,
+    /// Service locator
+    ///
+    /// <https://datatracker.ietf.org/doc/html/rfc2782>
+    SRV,
+    /// Certification Authority Authorization
+    ///
+    /// <https://datatracker.ietf.org/doc/html/rfc8659>
+    CAA,
+    /// General-purpose service binding
+    ///
+    /// <https://datatracker.ietf.org/doc/html/rfc9460>
+    SVCB,
+    /// Service binding for HTTPS
+    ///
+    /// <https://datatracker.ietf.org/doc/html/rfc9460#section-9>
+    HTTPS,
+    /// TLS certificate association, for DANE
+    ///
+    /// <https://datatracker.ietf.org/doc/html/rfc6698>
+    TLSA,
+    /// SSH public key fingerprint
+    ///
+    /// <https://datatracker.ietf.org/doc/html/rfc4255>
+    SSHFP,
+    /// Redirection for a subtree of names
+    ///
+    /// <https://datatracker.ietf.org/doc/html/rfc6672>
+    DNAME,
+    /// Naming authority pointer, for ENUM and SIP
+    ///
+    /// <https://datatracker.ietf.org/doc/html/rfc3403>
+    NAPTR,
+    /// Uniform Resource Identifier
+    ///
+    /// <https://datatracker.ietf.org/doc/html/rfc7553>
+    URI,
     ANY,
     Unknown(u16),
 }
@@ -48,6 +85,15 @@ impl DnsType {
             12 => DnsType::PTR,
             6 => DnsType::SOA,
             16 => DnsType::TXT,
+            33 => DnsType::SRV,
+            257 => DnsType::CAA,
+            64 => DnsType::SVCB,
+            65 => DnsType::HTTPS,
+            52 => DnsType::TLSA,
+            44 => DnsType::SSHFP,
+            39 => DnsType::DNAME,
+            35 => DnsType::NAPTR,
+            256 => DnsType::URI,
             255 => DnsType::ANY,
             other => DnsType::Unknown(other),
         }
@@ -64,11 +110,57 @@ impl DnsType {
             DnsType::PTR => 12,
             DnsType::SOA => 6,
             DnsType::TXT => 16,
+            DnsType::SRV => 33,
+            DnsType::CAA => 257,
+            DnsType::SVCB => 64,
+            DnsType::HTTPS => 65,
+            DnsType::TLSA => 52,
+            DnsType::SSHFP => 44,
+            DnsType::DNAME => 39,
+            DnsType::NAPTR => 35,
+            DnsType::URI => 256,
             DnsType::ANY => 255,
             DnsType::Unknown(other) => *other,
         }
     }
 
+    /// Parses a type mnemonic, like `MX`, or the generic `TYPEnnn` form.
+    ///
+    /// > ... an unknown type number may be represented by the word "TYPE" immediately followed
+    /// > by the decimal RR type number, with no intervening whitespace.
+    ///
+    /// <https://datatracker.ietf.org/doc/html/rfc3597#section-5>
+    ///
+    /// # Errors
+    /// Returns an error when `mnemonic` is not a known type or `TYPEnnn`.
+    pub fn parse(mnemonic: &str) -> Result<Self, String> {
+        let upper = mnemonic.to_ascii_uppercase();
+        match upper.as_str() {
+            "A" => Ok(DnsType::A),
+            "AAAA" => Ok(DnsType::AAAA),
+            "CNAME" => Ok(DnsType::CNAME),
+            "MX" => Ok(DnsType::MX),
+            "NS" => Ok(DnsType::NS),
+            "PTR" => Ok(DnsType::PTR),
+            "SOA" => Ok(DnsType::SOA),
+            "TXT" => Ok(DnsType::TXT),
+            "SRV" => Ok(DnsType::SRV),
+            "CAA" => Ok(DnsType::CAA),
+            "SVCB" => Ok(DnsType::SVCB),
+            "HTTPS" => Ok(DnsType::HTTPS),
+            "TLSA" => Ok(DnsType::TLSA),
+            "SSHFP" => Ok(DnsType::SSHFP),
+            "DNAME" => Ok(DnsType::DNAME),
+            "NAPTR" => Ok(DnsType::NAPTR),
+            "URI" => Ok(DnsType::URI),
+            "ANY" => Ok(DnsType::ANY),
+            _ => match upper.strip_prefix("TYPE") {
+                Some(digits) => parse_number(digits.as_bytes(), "type").map(Self::new),
+                None => Err(format!("unknown DNS type: {mnemonic:?}")),
+            },
+        }
+    }
+
     /// # Errors
     /// Returns an error when `buf` does not contain a valid two-byte type code.
     pub fn read<const N: usize>(buf: &mut FixedBuf<N>) -> Result<Self, DnsError> {
@@ -92,8 +184,17 @@ impl Display for DnsType {
             DnsType::PTR => write!(f, "PTR"),
             DnsType::SOA => write!(f, "SOA"),
             DnsType::TXT => write!(f, "TXT"),
+            DnsType::SRV => write!(f, "SRV"),
+            DnsType::CAA => write!(f, "CAA"),
+            DnsType::SVCB => write!(f, "SVCB"),
+            DnsType::HTTPS => write!(f, "HTTPS"),
+            DnsType::TLSA => write!(f, "TLSA"),
+            DnsType::SSHFP => write!(f, "SSHFP"),
+            DnsType::DNAME => write!(f, "DNAME"),
+            DnsType::NAPTR => write!(f, "NAPTR"),
+            DnsType::URI => write!(f, "URI"),
             DnsType::ANY => write!(f, "ANY"),
-            DnsType::Unknown(n) => write!(f, "Unknown({n})"),
+            DnsType::Unknown(n) => write!(f, "TYPE{n}"),
         }
     }
 }
@@ -23,10 +23,21 @@
 //!   Servers on port 80 can use HTTP for domain validation and don't need to use this.
 //!
 //! # Features
-//! - Depends only on `std`
+//! - Depends on `std` and `fixed-buffer`, plus optional dependencies behind Cargo features
 //! - `forbid(unsafe_code)`
+//! - Internationalized domain names
+//! - TLSA records from certificates
+//! - SSHFP records from OpenSSH public keys
 //! - ?% test coverage
 //!
+//! # Cargo Features
+//! All features are optional and enabled by default.
+//! Build with `default-features = false` to drop their dependencies.
+//! - `idna` - [`DnsName::from_unicode`] and [`DnsName::to_unicode`] for internationalized names.
+//! - `sha2` - [`DnsRecord::new_tlsa_from_der`], which hashes a DER certificate with SHA-256.
+//! - `sha1` and `sha2` together - [`DnsRecord::new_sshfp_from_public_key`],
+//!   which makes SHA-1 and SHA-256 fingerprints of an OpenSSH public key.
+//!
 //! # Limitations
 //! - Brand new.
 //!
@@ -65,14 +76,15 @@
 //!
 //! # Cargo Geiger Safety Report
 //! # Changelog
+//! - Unreleased
+//!   - Requires Rust 1.87 or later, declared as `rust-version` in `Cargo.toml`.
+//!   - `DnsName::inner` returns a `String` instead of a `&str`, because names are now stored
+//!     in wire format.  It is deprecated.  Use `to_string()` instead.
 //! - v0.1.0 - Initial version
 //!
 //! # To Do
-//! - Message compression
 //! - Decide whether to send back error responses.
 //! - Ergonomic constructors that take `OsStr`, for using environment variables
-//! - Custom TTLs
-//! - NS records (and glue)
 //! - Client
 //! - Caching client
 //! - Recursive resolver
@@ -85,20 +97,26 @@ mod dns_class;
 mod dns_message;
 mod dns_message_header;
 mod dns_name;
+mod dns_name_compressor;
+mod dns_name_syntax;
 mod dns_op_code;
+mod dns_presentation;
 mod dns_question;
 mod dns_record;
 mod dns_response_code;
+mod dns_svc_param;
 mod dns_type;
 
 pub use dns_class::DnsClass;
 pub use dns_message::DnsMessage;
 pub use dns_message_header::DnsMessageHeader;
 pub use dns_name::DnsName;
+pub use dns_name_syntax::DnsNameSyntax;
 pub use dns_op_code::DnsOpCode;
 pub use dns_question::DnsQuestion;
 pub use dns_record::DnsRecord;
 pub use dns_response_code::DnsResponseCode;
+pub use dns_svc_param::DnsSvcParam;
 pub use dns_type::DnsType;
 
 use fixed_buffer::FixedBuf;
@@ -109,6 +127,11 @@ fn read_exact<const N: usize, const M: usize>(buf: &mut FixedBuf<N>) -> Result<[
     Ok(result)
 }
 
+/// Returns the offset of the next unread byte from the start of `buf`'s memory.
+fn read_position<const N: usize>(buf: &mut FixedBuf<N>) -> usize {
+    N - buf.writable().len() - buf.len()
+}
+
 fn read_u8<const N: usize>(buf: &mut FixedBuf<N>) -> Result<u8, DnsError> {
     buf.try_read_byte().ok_or(DnsError::Truncated)
 }
@@ -154,6 +177,8 @@ pub enum DnsError {
     InvalidClass,
     InvalidLabel,
     InvalidOpCode,
+    InvalidPointer,
+    InvalidRdata,
     NameTooLong,
     NoQuestion,
     NotARequest,